@@ -1,13 +1,36 @@
 use bitflags::BitFlags;
-use serde::de::{Error, Visitor};
+use serde::de::{Error, Unexpected, Visitor};
 use std::fmt;
 use std::marker::PhantomData;
 
-pub struct BitFlagsVisitor<T>(PhantomData<T>);
+pub struct BitFlagsVisitor<T> {
+	retain: bool,
+	flags: PhantomData<T>,
+}
 
 impl<T> BitFlagsVisitor<T> {
+	// Drops bits that don't correspond to a known flag
 	pub fn new() -> Self {
-		Self(PhantomData)
+		Self {
+			retain: false,
+			flags: PhantomData,
+		}
+	}
+
+	// Keeps unknown bits, so they survive being sent back to Discord
+	pub fn retain() -> Self {
+		Self {
+			retain: true,
+			flags: PhantomData,
+		}
+	}
+
+	fn flags<B: BitFlags<Bits = u64>>(&self, bits: u64) -> B {
+		if self.retain {
+			B::from_bits_retain(bits)
+		} else {
+			B::from_bits_truncate(bits)
+		}
 	}
 }
 
@@ -22,8 +45,15 @@ impl<'de, T: BitFlags<Bits = u64>> Visitor<'de> for BitFlagsVisitor<T> {
 	where
 		E: Error,
 	{
-		Ok(T::from_bits_truncate(
-			v.try_into().map_err(|_| E::custom("invalid value"))?,
-		))
+		Ok(self.flags(v))
+	}
+
+	fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		v.parse()
+			.map(|v| self.flags(v))
+			.map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
 	}
 }
@@ -1,6 +1,8 @@
-use crate::{ChannelId, CowString, GuildId, Intents, Status, UserId};
+use crate::{Activity, ChannelId, CowString, GuildId, Intents, Status, UserId};
+use serde::de;
+use serde::de::{IgnoredAny, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashSet;
 use std::fmt;
 
@@ -12,6 +14,8 @@ pub enum Command {
 	UpdateVoiceState(UpdateVoiceState),
 	Resume(Resume),
 	RequestGuildMembers(RequestGuildMembers),
+	// Opcode and raw payload of a command the crate doesn't model
+	Unknown(u8, serde_json::Value),
 }
 
 impl Command {
@@ -23,14 +27,12 @@ impl Command {
 			Command::UpdateVoiceState(_) => 4,
 			Command::Resume(_) => 6,
 			Command::RequestGuildMembers(_) => 8,
+			Command::Unknown(op, _) => *op,
 		}
 	}
 
 	pub fn is_heartbeat(&self) -> bool {
-		match self {
-			Command::Heartbeat(_) => true,
-			_ => false,
-		}
+		matches!(self, Command::Heartbeat(_))
 	}
 
 	pub fn heartbeat<T: Into<Heartbeat>>(heartbeat: T) -> Self {
@@ -47,6 +49,7 @@ impl fmt::Display for Command {
 			Command::RequestGuildMembers(_) => write!(f, "RequestGuildMembers"),
 			Command::UpdateVoiceState(_) => write!(f, "UpdateVoiceState"),
 			Command::UpdateStatus(_) => write!(f, "UpdateStatus"),
+			Command::Unknown(op, _) => write!(f, "Unknown({})", op),
 		}
 	}
 }
@@ -65,12 +68,71 @@ impl Serialize for Command {
 			Command::UpdateVoiceState(d) => state.serialize_field("d", d)?,
 			Command::Resume(d) => state.serialize_field("d", d)?,
 			Command::RequestGuildMembers(d) => state.serialize_field("d", d)?,
+			Command::Unknown(_, d) => state.serialize_field("d", d)?,
 		}
 		state.end()
 	}
 }
 
-#[derive(Clone, Debug, Serialize)]
+impl<'de> Deserialize<'de> for Command {
+	fn deserialize<D>(deserializer: D) -> Result<Command, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct CommandVisitor;
+
+		impl<'de> Visitor<'de> for CommandVisitor {
+			type Value = Command;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("struct Command")
+			}
+
+			fn visit_map<V>(self, mut map: V) -> Result<Command, V::Error>
+			where
+				V: MapAccess<'de>,
+			{
+				let mut op: Option<u8> = None;
+				let mut command = None;
+				while let Some(key) = map.next_key()? {
+					match key {
+						"op" => {
+							if op.is_some() {
+								return Err(de::Error::duplicate_field("op"));
+							}
+							op = Some(map.next_value()?);
+						}
+						"d" => {
+							if command.is_some() {
+								return Err(de::Error::duplicate_field("d"));
+							}
+
+							let op = op.ok_or_else(|| de::Error::missing_field("op"))?;
+							let c = match op {
+								1 => Command::Heartbeat(map.next_value()?),
+								2 => Command::Identify(map.next_value()?),
+								3 => Command::UpdateStatus(map.next_value()?),
+								4 => Command::UpdateVoiceState(map.next_value()?),
+								6 => Command::Resume(map.next_value()?),
+								8 => Command::RequestGuildMembers(map.next_value()?),
+								e => Command::Unknown(e, map.next_value()?),
+							};
+							command = Some(c);
+						}
+						_ => {
+							map.next_value::<IgnoredAny>()?;
+						}
+					}
+				}
+				command.ok_or_else(|| de::Error::missing_field("d"))
+			}
+		}
+
+		deserializer.deserialize_struct("Command", &["op", "d"], CommandVisitor)
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Identify {
 	pub token: CowString,
 	pub properties: ConnectionProperties,
@@ -94,7 +156,7 @@ impl From<Identify> for Command {
 	}
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Resume {
 	pub token: CowString,
 	pub session_id: CowString,
@@ -107,19 +169,22 @@ impl From<Resume> for Command {
 	}
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct Heartbeat {
-	pub sequence: u64,
+	pub sequence: Option<u64>,
 }
 
 impl fmt::Display for Heartbeat {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		write!(f, "{}", self.sequence)
+		match self.sequence {
+			Some(seq) => write!(f, "{}", seq),
+			None => write!(f, "null"),
+		}
 	}
 }
 
-impl<'a> From<Heartbeat> for Command {
+impl From<Heartbeat> for Command {
 	fn from(heartbeat: Heartbeat) -> Command {
 		Command::Heartbeat(heartbeat)
 	}
@@ -127,18 +192,26 @@ impl<'a> From<Heartbeat> for Command {
 
 impl From<u64> for Heartbeat {
 	fn from(sequence: u64) -> Self {
+		Heartbeat {
+			sequence: Some(sequence),
+		}
+	}
+}
+
+impl From<Option<u64>> for Heartbeat {
+	fn from(sequence: Option<u64>) -> Self {
 		Heartbeat { sequence }
 	}
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RequestGuildMembers {
 	pub guild_id: GuildId,
 	pub query: CowString,
 	pub limit: u32,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub presences: Option<bool>,
-	#[serde(skip_serializing_if = "HashSet::is_empty")]
+	#[serde(default, skip_serializing_if = "HashSet::is_empty")]
 	pub user_ids: HashSet<UserId>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub nonce: Option<CowString>,
@@ -150,7 +223,7 @@ impl From<RequestGuildMembers> for Command {
 	}
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct UpdateVoiceState {
 	pub guild_id: GuildId,
 	pub channel_id: Option<ChannelId>,
@@ -164,10 +237,11 @@ impl From<UpdateVoiceState> for Command {
 	}
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct UpdateStatus {
 	pub since: Option<u64>,
-	// pub activities: Vec<Activity>,
+	#[serde(default)]
+	pub activities: Vec<Activity>,
 	pub status: Status,
 	pub afk: bool,
 }
@@ -178,7 +252,7 @@ impl From<UpdateStatus> for Command {
 	}
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ConnectionProperties {
 	#[serde(rename = "$os")]
 	pub os: CowString,
@@ -187,3 +261,50 @@ pub struct ConnectionProperties {
 	#[serde(rename = "$device")]
 	pub device: CowString,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn round_trip(json: &str) -> Command {
+		let command: Command = serde_json::from_str(json).unwrap();
+		let value = serde_json::to_value(&command).unwrap();
+		let original: serde_json::Value = serde_json::from_str(json).unwrap();
+		assert_eq!(value, original);
+		command
+	}
+
+	#[test]
+	fn deserialize() {
+		let identify = round_trip(
+			r#"{"op":2,"d":{"token":"tok","properties":{"$os":"linux","$browser":"bot","$device":"bot"},"shard":[0,2],"intents":513}}"#,
+		);
+		match identify {
+			Command::Identify(i) => {
+				assert_eq!(i.intents, Some(Intents::GUILDS | Intents::GUILD_MESSAGES))
+			}
+			c => panic!("unexpected command {}", c),
+		}
+		assert!(round_trip(r#"{"op":1,"d":5}"#).is_heartbeat());
+		round_trip(r#"{"op":1,"d":null}"#);
+		round_trip(r#"{"op":3,"d":{"since":null,"activities":[],"status":"online","afk":false}}"#);
+		round_trip(
+			r#"{"op":3,"d":{"since":null,"activities":[{"name":"Stream","type":1,"url":"https://twitch.tv/a"}],"status":"idle","afk":true}}"#,
+		);
+		round_trip(
+			r#"{"op":4,"d":{"guild_id":"1","channel_id":null,"self_mute":false,"self_deaf":true}}"#,
+		);
+		round_trip(r#"{"op":6,"d":{"token":"tok","session_id":"abc","seq":10}}"#);
+		round_trip(
+			r#"{"op":8,"d":{"guild_id":"1","query":"","limit":0,"user_ids":["2"],"nonce":"n"}}"#,
+		);
+		match round_trip(r#"{"op":31,"d":null}"#) {
+			Command::Unknown(31, serde_json::Value::Null) => {}
+			c => panic!("unexpected command {}", c),
+		}
+		match round_trip(r#"{"op":14,"d":{"guild_id":"1","members":true}}"#) {
+			Command::Unknown(14, d) => assert_eq!(d["guild_id"], "1"),
+			c => panic!("unexpected command {}", c),
+		}
+	}
+}
@@ -1,7 +1,9 @@
 use crate::*;
+use chrono::{TimeZone, Utc};
 use serde::de;
 use serde::de::{IgnoredAny, MapAccess, SeqAccess, Visitor};
-use serde::{Deserialize, Deserializer};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashSet;
 use std::fmt;
 
@@ -72,6 +74,14 @@ impl<'de> Deserialize<'de> for Payload {
 
 							let op = op.ok_or_else(|| de::Error::missing_field("op"))?;
 							let event = match (op, t.as_deref()) {
+								(1, _) => {
+									map.next_value::<IgnoredAny>()?;
+									Event::HeartbeatRequest
+								}
+								(7, _) => {
+									map.next_value::<IgnoredAny>()?;
+									Event::Reconnect
+								}
 								(9, _) => Event::InvalidSession(map.next_value()?),
 								(10, _) => Event::Hello(map.next_value()?),
 								(11, _) => {
@@ -90,9 +100,9 @@ impl<'de> Deserialize<'de> for Payload {
 									"MESSAGE_CREATE" => Event::MessageCreate(map.next_value()?),
 									"MESSAGE_UPDATE" => Event::MessageUpdate(map.next_value()?),
 									"MESSAGE_DELETE" => Event::MessageDelete(map.next_value()?),
-									/*"MESSAGE_DELETE_BULK" => {
+									"MESSAGE_DELETE_BULK" => {
 										Event::MessageDeleteBulk(map.next_value()?)
-									}*/
+									}
 									"GUILD_MEMBER_ADD" => Event::GuildMemberAdd(map.next_value()?),
 									"GUILD_MEMBER_UPDATE" => {
 										Event::GuildMemberUpdate(map.next_value()?)
@@ -115,7 +125,7 @@ impl<'de> Deserialize<'de> for Payload {
 									"CHANNEL_CREATE" => Event::ChannelCreate(map.next_value()?),
 									"CHANNEL_UPDATE" => Event::ChannelUpdate(map.next_value()?),
 									"CHANNEL_DELETE" => Event::ChannelDelete(map.next_value()?),
-									/*"MESSAGE_REACTION_ADD" => {
+									"MESSAGE_REACTION_ADD" => {
 										Event::MessageReactionAdd(map.next_value()?)
 									}
 									"MESSAGE_REACTION_REMOVE" => {
@@ -126,7 +136,7 @@ impl<'de> Deserialize<'de> for Payload {
 									}
 									"MESSAGE_REACTION_REMOVE_EMOJI" => {
 										Event::MessageReactionRemoveEmoji(map.next_value()?)
-									}*/
+									}
 									"APPLICATION_COMMAND_CREATE" => {
 										Event::ApplicationCommandCreate(map.next_value()?)
 									}
@@ -145,16 +155,98 @@ impl<'de> Deserialize<'de> for Payload {
 									"VOICE_SERVER_UPDATE" => {
 										Event::VoiceServerUpdate(map.next_value()?)
 									}
-									t => {
-										map.next_value::<IgnoredAny>()?;
-										Event::Unknown(t.into())
+									"PRESENCE_UPDATE" => Event::PresenceUpdate(map.next_value()?),
+									"TYPING_START" => Event::TypingStart(map.next_value()?),
+									"USER_UPDATE" => Event::UserUpdate(map.next_value()?),
+									"GUILD_BAN_ADD" => Event::GuildBanAdd(map.next_value()?),
+									"GUILD_BAN_REMOVE" => Event::GuildBanRemove(map.next_value()?),
+									"GUILD_EMOJIS_UPDATE" => {
+										Event::GuildEmojisUpdate(map.next_value()?)
+									}
+									"GUILD_STICKERS_UPDATE" => {
+										Event::GuildStickersUpdate(map.next_value()?)
+									}
+									"THREAD_CREATE" => Event::ThreadCreate(map.next_value()?),
+									"THREAD_UPDATE" => Event::ThreadUpdate(map.next_value()?),
+									"THREAD_DELETE" => Event::ThreadDelete(map.next_value()?),
+									"THREAD_LIST_SYNC" => Event::ThreadListSync(map.next_value()?),
+									"THREAD_MEMBER_UPDATE" => {
+										Event::ThreadMemberUpdate(map.next_value()?)
+									}
+									"THREAD_MEMBERS_UPDATE" => {
+										Event::ThreadMembersUpdate(map.next_value()?)
+									}
+									"GUILD_SCHEDULED_EVENT_CREATE" => {
+										Event::GuildScheduledEventCreate(map.next_value()?)
+									}
+									"GUILD_SCHEDULED_EVENT_UPDATE" => {
+										Event::GuildScheduledEventUpdate(map.next_value()?)
+									}
+									"GUILD_SCHEDULED_EVENT_DELETE" => {
+										Event::GuildScheduledEventDelete(map.next_value()?)
+									}
+									"GUILD_SCHEDULED_EVENT_USER_ADD" => {
+										Event::GuildScheduledEventUserAdd(map.next_value()?)
+									}
+									"GUILD_SCHEDULED_EVENT_USER_REMOVE" => {
+										Event::GuildScheduledEventUserRemove(map.next_value()?)
+									}
+									"AUTO_MODERATION_RULE_CREATE" => {
+										Event::AutoModerationRuleCreate(map.next_value()?)
+									}
+									"AUTO_MODERATION_RULE_UPDATE" => {
+										Event::AutoModerationRuleUpdate(map.next_value()?)
+									}
+									"AUTO_MODERATION_RULE_DELETE" => {
+										Event::AutoModerationRuleDelete(map.next_value()?)
+									}
+									"AUTO_MODERATION_ACTION_EXECUTION" => {
+										Event::AutoModerationActionExecution(map.next_value()?)
+									}
+									"INVITE_CREATE" => Event::InviteCreate(map.next_value()?),
+									"INVITE_DELETE" => Event::InviteDelete(map.next_value()?),
+									"CHANNEL_PINS_UPDATE" => {
+										Event::ChannelPinsUpdate(map.next_value()?)
+									}
+									"WEBHOOKS_UPDATE" => Event::WebhooksUpdate(map.next_value()?),
+									"STAGE_INSTANCE_CREATE" => {
+										Event::StageInstanceCreate(map.next_value()?)
+									}
+									"STAGE_INSTANCE_UPDATE" => {
+										Event::StageInstanceUpdate(map.next_value()?)
+									}
+									"STAGE_INSTANCE_DELETE" => {
+										Event::StageInstanceDelete(map.next_value()?)
+									}
+									"INTEGRATION_CREATE" => {
+										Event::IntegrationCreate(map.next_value()?)
+									}
+									"INTEGRATION_UPDATE" => {
+										Event::IntegrationUpdate(map.next_value()?)
+									}
+									"INTEGRATION_DELETE" => {
+										Event::IntegrationDelete(map.next_value()?)
+									}
+									"GUILD_INTEGRATIONS_UPDATE" => {
+										Event::GuildIntegrationsUpdate(map.next_value()?)
 									}
+									"APPLICATION_COMMAND_PERMISSIONS_UPDATE" => {
+										Event::ApplicationCommandPermissionsUpdate(
+											map.next_value()?,
+										)
+									}
+									t => Event::Unknown(UnknownEvent {
+										op,
+										name: Some(t.into()),
+										data: map.next_value()?,
+									}),
 								},
 								(0, None) => return Err(de::Error::missing_field("t")),
-								(e, _) => {
-									map.next_value::<IgnoredAny>()?;
-									Event::Unknown(format!("{}", e))
-								}
+								(op, t) => Event::Unknown(UnknownEvent {
+									op,
+									name: t.map(Into::into),
+									data: map.next_value()?,
+								}),
 							};
 							payload = Some(Payload { sequence, event });
 						}
@@ -171,12 +263,98 @@ impl<'de> Deserialize<'de> for Payload {
 	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl Serialize for Payload {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut state = serializer.serialize_struct("Payload", 4)?;
+		state.serialize_field("t", &self.event.name())?;
+		state.serialize_field("s", &self.sequence)?;
+		state.serialize_field("op", &self.event.op())?;
+		match &self.event {
+			Event::Hello(d) => state.serialize_field("d", d)?,
+			Event::Ready(d) => state.serialize_field("d", d)?,
+			Event::InvalidSession(d) => state.serialize_field("d", d)?,
+			Event::GuildCreate(d) => state.serialize_field("d", d)?,
+			Event::GuildUpdate(d) => state.serialize_field("d", d)?,
+			Event::GuildDelete(d) => state.serialize_field("d", d)?,
+			Event::MessageCreate(d) => state.serialize_field("d", d)?,
+			Event::MessageUpdate(d) => state.serialize_field("d", d)?,
+			Event::MessageDelete(d) => state.serialize_field("d", d)?,
+			Event::MessageDeleteBulk(d) => state.serialize_field("d", d)?,
+			Event::GuildMemberAdd(d) => state.serialize_field("d", d)?,
+			Event::GuildMemberUpdate(d) => state.serialize_field("d", d)?,
+			Event::GuildMemberRemove(d) => state.serialize_field("d", d)?,
+			Event::GuildMembersChunk(d) => state.serialize_field("d", d)?,
+			Event::GuildRoleCreate(d) => state.serialize_field("d", d)?,
+			Event::GuildRoleUpdate(d) => state.serialize_field("d", d)?,
+			Event::GuildRoleDelete(d) => state.serialize_field("d", d)?,
+			Event::ChannelCreate(d) => state.serialize_field("d", d)?,
+			Event::ChannelUpdate(d) => state.serialize_field("d", d)?,
+			Event::ChannelDelete(d) => state.serialize_field("d", d)?,
+			Event::MessageReactionAdd(d) => state.serialize_field("d", d)?,
+			Event::MessageReactionRemove(d) => state.serialize_field("d", d)?,
+			Event::MessageReactionRemoveAll(d) => state.serialize_field("d", d)?,
+			Event::MessageReactionRemoveEmoji(d) => state.serialize_field("d", d)?,
+			Event::ApplicationCommandCreate(d) => state.serialize_field("d", d)?,
+			Event::ApplicationCommandUpdate(d) => state.serialize_field("d", d)?,
+			Event::ApplicationCommandDelete(d) => state.serialize_field("d", d)?,
+			Event::InteractionCreate(d) => state.serialize_field("d", d)?,
+			Event::VoiceStateUpdate(d) => state.serialize_field("d", d)?,
+			Event::VoiceServerUpdate(d) => state.serialize_field("d", d)?,
+			Event::PresenceUpdate(d) => state.serialize_field("d", d)?,
+			Event::TypingStart(d) => state.serialize_field("d", d)?,
+			Event::UserUpdate(d) => state.serialize_field("d", d)?,
+			Event::GuildBanAdd(d) => state.serialize_field("d", d)?,
+			Event::GuildBanRemove(d) => state.serialize_field("d", d)?,
+			Event::GuildEmojisUpdate(d) => state.serialize_field("d", d)?,
+			Event::GuildStickersUpdate(d) => state.serialize_field("d", d)?,
+			Event::ThreadCreate(d) => state.serialize_field("d", d)?,
+			Event::ThreadUpdate(d) => state.serialize_field("d", d)?,
+			Event::ThreadDelete(d) => state.serialize_field("d", d)?,
+			Event::ThreadListSync(d) => state.serialize_field("d", d)?,
+			Event::ThreadMemberUpdate(d) => state.serialize_field("d", d)?,
+			Event::ThreadMembersUpdate(d) => state.serialize_field("d", d)?,
+			Event::GuildScheduledEventCreate(d) => state.serialize_field("d", d)?,
+			Event::GuildScheduledEventUpdate(d) => state.serialize_field("d", d)?,
+			Event::GuildScheduledEventDelete(d) => state.serialize_field("d", d)?,
+			Event::GuildScheduledEventUserAdd(d) => state.serialize_field("d", d)?,
+			Event::GuildScheduledEventUserRemove(d) => state.serialize_field("d", d)?,
+			Event::AutoModerationRuleCreate(d) => state.serialize_field("d", d)?,
+			Event::AutoModerationRuleUpdate(d) => state.serialize_field("d", d)?,
+			Event::AutoModerationRuleDelete(d) => state.serialize_field("d", d)?,
+			Event::AutoModerationActionExecution(d) => state.serialize_field("d", d)?,
+			Event::InviteCreate(d) => state.serialize_field("d", d)?,
+			Event::InviteDelete(d) => state.serialize_field("d", d)?,
+			Event::ChannelPinsUpdate(d) => state.serialize_field("d", d)?,
+			Event::WebhooksUpdate(d) => state.serialize_field("d", d)?,
+			Event::StageInstanceCreate(d) => state.serialize_field("d", d)?,
+			Event::StageInstanceUpdate(d) => state.serialize_field("d", d)?,
+			Event::StageInstanceDelete(d) => state.serialize_field("d", d)?,
+			Event::IntegrationCreate(d) => state.serialize_field("d", d)?,
+			Event::IntegrationUpdate(d) => state.serialize_field("d", d)?,
+			Event::IntegrationDelete(d) => state.serialize_field("d", d)?,
+			Event::GuildIntegrationsUpdate(d) => state.serialize_field("d", d)?,
+			Event::ApplicationCommandPermissionsUpdate(d) => state.serialize_field("d", d)?,
+			Event::Resumed | Event::Reconnect | Event::HeartbeatRequest | Event::HeartbeatAck => {
+				state.serialize_field("d", &())?
+			}
+			Event::Unknown(e) => state.serialize_field("d", &e.data)?,
+		}
+		state.end()
+	}
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum Event {
 	Hello(Hello),
 	Ready(Ready),
 	Resumed,
+	Reconnect,
 	InvalidSession(InvalidSession),
+	HeartbeatRequest,
 	HeartbeatAck,
 	GuildCreate(GuildCreate),
 	GuildUpdate(GuildUpdate),
@@ -184,7 +362,7 @@ pub enum Event {
 	MessageCreate(MessageCreate),
 	MessageUpdate(MessageUpdate),
 	MessageDelete(MessageDelete),
-	// MessageDeleteBulk(MessageDeleteBulk),
+	MessageDeleteBulk(MessageDeleteBulk),
 	GuildMemberAdd(GuildMemberAdd),
 	GuildMemberUpdate(GuildMemberUpdate),
 	GuildMemberRemove(GuildMemberRemove),
@@ -195,20 +373,140 @@ pub enum Event {
 	ChannelCreate(ChannelCreate),
 	ChannelUpdate(ChannelUpdate),
 	ChannelDelete(ChannelDelete),
-	// MessageReactionAdd(MessageReactionAdd),
-	// MessageReactionRemove(MessageReactionRemove),
-	// MessageReactionRemoveAll(MessageReactionRemoveAll),
-	// MessageReactionRemoveEmoji(MessageReactionRemoveEmoji),
+	MessageReactionAdd(MessageReactionAdd),
+	MessageReactionRemove(MessageReactionRemove),
+	MessageReactionRemoveAll(MessageReactionRemoveAll),
+	MessageReactionRemoveEmoji(MessageReactionRemoveEmoji),
 	ApplicationCommandCreate(ApplicationCommandCreate),
 	ApplicationCommandUpdate(ApplicationCommandUpdate),
 	ApplicationCommandDelete(ApplicationCommandDelete),
 	InteractionCreate(InteractionCreate),
 	VoiceStateUpdate(VoiceStateUpdate),
 	VoiceServerUpdate(VoiceServerUpdate),
-	Unknown(String),
+	PresenceUpdate(PresenceUpdate),
+	TypingStart(TypingStart),
+	UserUpdate(UserUpdate),
+	GuildBanAdd(GuildBanAdd),
+	GuildBanRemove(GuildBanRemove),
+	GuildEmojisUpdate(GuildEmojisUpdate),
+	GuildStickersUpdate(GuildStickersUpdate),
+	ThreadCreate(ThreadCreate),
+	ThreadUpdate(ThreadUpdate),
+	ThreadDelete(ThreadDelete),
+	ThreadListSync(ThreadListSync),
+	ThreadMemberUpdate(ThreadMemberUpdate),
+	ThreadMembersUpdate(ThreadMembersUpdate),
+	GuildScheduledEventCreate(GuildScheduledEventCreate),
+	GuildScheduledEventUpdate(GuildScheduledEventUpdate),
+	GuildScheduledEventDelete(GuildScheduledEventDelete),
+	GuildScheduledEventUserAdd(GuildScheduledEventUserAdd),
+	GuildScheduledEventUserRemove(GuildScheduledEventUserRemove),
+	AutoModerationRuleCreate(AutoModerationRuleCreate),
+	AutoModerationRuleUpdate(AutoModerationRuleUpdate),
+	AutoModerationRuleDelete(AutoModerationRuleDelete),
+	AutoModerationActionExecution(AutoModerationActionExecution),
+	InviteCreate(InviteCreate),
+	InviteDelete(InviteDelete),
+	ChannelPinsUpdate(ChannelPinsUpdate),
+	WebhooksUpdate(WebhooksUpdate),
+	StageInstanceCreate(StageInstanceCreate),
+	StageInstanceUpdate(StageInstanceUpdate),
+	StageInstanceDelete(StageInstanceDelete),
+	IntegrationCreate(IntegrationCreate),
+	IntegrationUpdate(IntegrationUpdate),
+	IntegrationDelete(IntegrationDelete),
+	GuildIntegrationsUpdate(GuildIntegrationsUpdate),
+	ApplicationCommandPermissionsUpdate(ApplicationCommandPermissionsUpdate),
+	Unknown(UnknownEvent),
 }
 
 impl Event {
+	pub fn op(&self) -> u8 {
+		match self {
+			Event::HeartbeatRequest => 1,
+			Event::Reconnect => 7,
+			Event::InvalidSession(_) => 9,
+			Event::Hello(_) => 10,
+			Event::HeartbeatAck => 11,
+			Event::Unknown(e) => e.op,
+			_ => 0,
+		}
+	}
+
+	// Dispatch event name, as sent in the `t` field
+	pub fn name(&self) -> Option<&str> {
+		let name = match self {
+			Event::Ready(_) => "READY",
+			Event::Resumed => "RESUMED",
+			Event::GuildCreate(_) => "GUILD_CREATE",
+			Event::GuildUpdate(_) => "GUILD_UPDATE",
+			Event::GuildDelete(_) => "GUILD_DELETE",
+			Event::MessageCreate(_) => "MESSAGE_CREATE",
+			Event::MessageUpdate(_) => "MESSAGE_UPDATE",
+			Event::MessageDelete(_) => "MESSAGE_DELETE",
+			Event::MessageDeleteBulk(_) => "MESSAGE_DELETE_BULK",
+			Event::GuildMemberAdd(_) => "GUILD_MEMBER_ADD",
+			Event::GuildMemberUpdate(_) => "GUILD_MEMBER_UPDATE",
+			Event::GuildMemberRemove(_) => "GUILD_MEMBER_REMOVE",
+			Event::GuildMembersChunk(_) => "GUILD_MEMBERS_CHUNK",
+			Event::GuildRoleCreate(_) => "GUILD_ROLE_CREATE",
+			Event::GuildRoleUpdate(_) => "GUILD_ROLE_UPDATE",
+			Event::GuildRoleDelete(_) => "GUILD_ROLE_DELETE",
+			Event::ChannelCreate(_) => "CHANNEL_CREATE",
+			Event::ChannelUpdate(_) => "CHANNEL_UPDATE",
+			Event::ChannelDelete(_) => "CHANNEL_DELETE",
+			Event::MessageReactionAdd(_) => "MESSAGE_REACTION_ADD",
+			Event::MessageReactionRemove(_) => "MESSAGE_REACTION_REMOVE",
+			Event::MessageReactionRemoveAll(_) => "MESSAGE_REACTION_REMOVE_ALL",
+			Event::MessageReactionRemoveEmoji(_) => "MESSAGE_REACTION_REMOVE_EMOJI",
+			Event::ApplicationCommandCreate(_) => "APPLICATION_COMMAND_CREATE",
+			Event::ApplicationCommandUpdate(_) => "APPLICATION_COMMAND_UPDATE",
+			Event::ApplicationCommandDelete(_) => "APPLICATION_COMMAND_DELETE",
+			Event::InteractionCreate(_) => "INTERACTION_CREATE",
+			Event::VoiceStateUpdate(_) => "VOICE_STATE_UPDATE",
+			Event::VoiceServerUpdate(_) => "VOICE_SERVER_UPDATE",
+			Event::PresenceUpdate(_) => "PRESENCE_UPDATE",
+			Event::TypingStart(_) => "TYPING_START",
+			Event::UserUpdate(_) => "USER_UPDATE",
+			Event::GuildBanAdd(_) => "GUILD_BAN_ADD",
+			Event::GuildBanRemove(_) => "GUILD_BAN_REMOVE",
+			Event::GuildEmojisUpdate(_) => "GUILD_EMOJIS_UPDATE",
+			Event::GuildStickersUpdate(_) => "GUILD_STICKERS_UPDATE",
+			Event::ThreadCreate(_) => "THREAD_CREATE",
+			Event::ThreadUpdate(_) => "THREAD_UPDATE",
+			Event::ThreadDelete(_) => "THREAD_DELETE",
+			Event::ThreadListSync(_) => "THREAD_LIST_SYNC",
+			Event::ThreadMemberUpdate(_) => "THREAD_MEMBER_UPDATE",
+			Event::ThreadMembersUpdate(_) => "THREAD_MEMBERS_UPDATE",
+			Event::GuildScheduledEventCreate(_) => "GUILD_SCHEDULED_EVENT_CREATE",
+			Event::GuildScheduledEventUpdate(_) => "GUILD_SCHEDULED_EVENT_UPDATE",
+			Event::GuildScheduledEventDelete(_) => "GUILD_SCHEDULED_EVENT_DELETE",
+			Event::GuildScheduledEventUserAdd(_) => "GUILD_SCHEDULED_EVENT_USER_ADD",
+			Event::GuildScheduledEventUserRemove(_) => "GUILD_SCHEDULED_EVENT_USER_REMOVE",
+			Event::AutoModerationRuleCreate(_) => "AUTO_MODERATION_RULE_CREATE",
+			Event::AutoModerationRuleUpdate(_) => "AUTO_MODERATION_RULE_UPDATE",
+			Event::AutoModerationRuleDelete(_) => "AUTO_MODERATION_RULE_DELETE",
+			Event::AutoModerationActionExecution(_) => "AUTO_MODERATION_ACTION_EXECUTION",
+			Event::InviteCreate(_) => "INVITE_CREATE",
+			Event::InviteDelete(_) => "INVITE_DELETE",
+			Event::ChannelPinsUpdate(_) => "CHANNEL_PINS_UPDATE",
+			Event::WebhooksUpdate(_) => "WEBHOOKS_UPDATE",
+			Event::StageInstanceCreate(_) => "STAGE_INSTANCE_CREATE",
+			Event::StageInstanceUpdate(_) => "STAGE_INSTANCE_UPDATE",
+			Event::StageInstanceDelete(_) => "STAGE_INSTANCE_DELETE",
+			Event::IntegrationCreate(_) => "INTEGRATION_CREATE",
+			Event::IntegrationUpdate(_) => "INTEGRATION_UPDATE",
+			Event::IntegrationDelete(_) => "INTEGRATION_DELETE",
+			Event::GuildIntegrationsUpdate(_) => "GUILD_INTEGRATIONS_UPDATE",
+			Event::ApplicationCommandPermissionsUpdate(_) => {
+				"APPLICATION_COMMAND_PERMISSIONS_UPDATE"
+			}
+			Event::Unknown(e) if e.op == 0 => return e.name.as_deref(),
+			_ => return None,
+		};
+		Some(name)
+	}
+
 	pub fn guild_id(&self) -> Option<GuildId> {
 		match self {
 			Event::GuildCreate(e) => Some(e.guild.id),
@@ -217,7 +515,7 @@ impl Event {
 			Event::MessageCreate(e) => e.message.guild_id,
 			Event::MessageUpdate(e) => e.guild_id,
 			Event::MessageDelete(e) => e.guild_id,
-			// Event::MessageDeleteBulk(e) => e.guild_id,
+			Event::MessageDeleteBulk(e) => e.guild_id,
 			Event::GuildMemberAdd(e) => Some(e.guild_id),
 			Event::GuildMemberUpdate(e) => Some(e.guild_id),
 			Event::GuildMemberRemove(e) => Some(e.guild_id),
@@ -228,16 +526,49 @@ impl Event {
 			Event::ChannelCreate(e) => e.channel.guild_id,
 			Event::ChannelUpdate(e) => e.channel.guild_id,
 			Event::ChannelDelete(e) => e.channel.guild_id,
-			// Event::MessageReactionAdd(e) => e.guild_id,
-			// Event::MessageReactionRemove(e) => e.guild_id,
-			// Event::MessageReactionRemoveAll(e) => e.guild_id,
-			// Event::MessageReactionRemoveEmoji(e) => e.guild_id,
+			Event::MessageReactionAdd(e) => e.guild_id,
+			Event::MessageReactionRemove(e) => e.guild_id,
+			Event::MessageReactionRemoveAll(e) => e.guild_id,
+			Event::MessageReactionRemoveEmoji(e) => e.guild_id,
 			Event::ApplicationCommandCreate(e) => e.guild_id,
 			Event::ApplicationCommandUpdate(e) => e.guild_id,
 			Event::ApplicationCommandDelete(e) => e.guild_id,
 			Event::InteractionCreate(e) => e.interaction.guild_id,
 			Event::VoiceStateUpdate(e) => e.voice_state.guild_id,
 			Event::VoiceServerUpdate(e) => Some(e.guild_id),
+			Event::PresenceUpdate(e) => Some(e.guild_id),
+			Event::TypingStart(e) => e.guild_id,
+			Event::GuildBanAdd(e) => Some(e.guild_id),
+			Event::GuildBanRemove(e) => Some(e.guild_id),
+			Event::GuildEmojisUpdate(e) => Some(e.guild_id),
+			Event::GuildStickersUpdate(e) => Some(e.guild_id),
+			Event::ThreadCreate(e) => e.channel.guild_id,
+			Event::ThreadUpdate(e) => e.channel.guild_id,
+			Event::ThreadDelete(e) => Some(e.guild_id),
+			Event::ThreadListSync(e) => Some(e.guild_id),
+			Event::ThreadMemberUpdate(e) => Some(e.guild_id),
+			Event::ThreadMembersUpdate(e) => Some(e.guild_id),
+			Event::GuildScheduledEventCreate(e) => Some(e.event.guild_id),
+			Event::GuildScheduledEventUpdate(e) => Some(e.event.guild_id),
+			Event::GuildScheduledEventDelete(e) => Some(e.event.guild_id),
+			Event::GuildScheduledEventUserAdd(e) => Some(e.guild_id),
+			Event::GuildScheduledEventUserRemove(e) => Some(e.guild_id),
+			Event::AutoModerationRuleCreate(e) => Some(e.rule.guild_id),
+			Event::AutoModerationRuleUpdate(e) => Some(e.rule.guild_id),
+			Event::AutoModerationRuleDelete(e) => Some(e.rule.guild_id),
+			Event::AutoModerationActionExecution(e) => Some(e.guild_id),
+			Event::InviteCreate(e) => e.guild_id,
+			Event::InviteDelete(e) => e.guild_id,
+			Event::ChannelPinsUpdate(e) => e.guild_id,
+			Event::WebhooksUpdate(e) => Some(e.guild_id),
+			Event::StageInstanceCreate(e) => Some(e.stage.guild_id),
+			Event::StageInstanceUpdate(e) => Some(e.stage.guild_id),
+			Event::StageInstanceDelete(e) => Some(e.stage.guild_id),
+			Event::IntegrationCreate(e) => Some(e.guild_id),
+			Event::IntegrationUpdate(e) => Some(e.guild_id),
+			Event::IntegrationDelete(e) => Some(e.guild_id),
+			Event::GuildIntegrationsUpdate(e) => Some(e.guild_id),
+			Event::ApplicationCommandPermissionsUpdate(e) => Some(e.permissions.guild_id),
 			_ => None,
 		}
 	}
@@ -257,17 +588,15 @@ impl Event {
 	}
 
 	pub fn is_heartbeat_ack(&self) -> bool {
-		match self {
-			Event::HeartbeatAck => true,
-			_ => false,
-		}
+		matches!(self, Event::HeartbeatAck)
+	}
+
+	pub fn is_reconnect(&self) -> bool {
+		matches!(self, Event::Reconnect)
 	}
 
 	pub fn is_invalid_session(&self) -> bool {
-		match self {
-			Event::InvalidSession(_) => true,
-			_ => false,
-		}
+		matches!(self, Event::InvalidSession(_))
 	}
 }
 
@@ -277,7 +606,9 @@ impl fmt::Display for Event {
 			Event::Hello(_) => write!(f, "Hello"),
 			Event::Ready(e) => write!(f, "Ready(username={})", e.user.username),
 			Event::Resumed => write!(f, "Resumed"),
+			Event::Reconnect => write!(f, "Reconnect"),
 			Event::InvalidSession(_) => write!(f, "InvalidSession"),
+			Event::HeartbeatRequest => write!(f, "HeartbeatRequest"),
 			Event::HeartbeatAck => write!(f, "HeartbeatAck"),
 			Event::GuildCreate(e) => {
 				write!(f, "GuildCreate(id={}, name={})", e.guild.id, e.guild.name)
@@ -302,12 +633,12 @@ impl fmt::Display for Event {
 			Event::MessageDelete(e) => {
 				write!(f, "MessageDelete(channel_id={}, id={})", e.channel_id, e.id)
 			}
-			/*Event::MessageDeleteBulk(e) => write!(
+			Event::MessageDeleteBulk(e) => write!(
 				f,
 				"MessageDeleteBulk(channel_id={}, count={})",
 				e.channel_id,
 				e.ids.len()
-			),*/
+			),
 			Event::GuildMemberAdd(e) => {
 				write!(f, "GuildMemberAdd(guild={}, user={})", e.guild_id, e.member)
 			}
@@ -344,7 +675,7 @@ impl fmt::Display for Event {
 			Event::ChannelCreate(e) => write!(f, "ChannelCreate(id={})", e.channel.id),
 			Event::ChannelUpdate(e) => write!(f, "ChannelUpdate(id={})", e.channel.id),
 			Event::ChannelDelete(e) => write!(f, "ChannelDelete(id={})", e.channel.id),
-			/*Event::MessageReactionAdd(e) => write!(
+			Event::MessageReactionAdd(e) => write!(
 				f,
 				"MessageReactionAdd(message={}, emoji={})",
 				e.message_id, e.emoji
@@ -361,7 +692,7 @@ impl fmt::Display for Event {
 				f,
 				"MessageReactionRemoveEmoji(message={}, emoji={})",
 				e.message_id, e.emoji
-			),*/
+			),
 			Event::ApplicationCommandCreate(e) => {
 				write!(f, "ApplicationCommandCreate(command={})", e.command.name)
 			}
@@ -393,78 +724,321 @@ impl fmt::Display for Event {
 				Ok(())
 			}
 			Event::VoiceServerUpdate(e) => write!(f, "VoiceServerUpdate(guild={})", e.guild_id),
-			Event::Unknown(n) => write!(f, "Unknown({})", n),
+			Event::PresenceUpdate(e) => write!(
+				f,
+				"PresenceUpdate(guild={}, user={})",
+				e.guild_id, e.user.id
+			),
+			Event::TypingStart(e) => write!(
+				f,
+				"TypingStart(channel_id={}, user={})",
+				e.channel_id, e.user_id
+			),
+			Event::UserUpdate(e) => write!(f, "UserUpdate(user={})", e.user),
+			Event::GuildBanAdd(e) => {
+				write!(f, "GuildBanAdd(guild={}, user={})", e.guild_id, e.user)
+			}
+			Event::GuildBanRemove(e) => {
+				write!(f, "GuildBanRemove(guild={}, user={})", e.guild_id, e.user)
+			}
+			Event::GuildEmojisUpdate(e) => write!(
+				f,
+				"GuildEmojisUpdate(guild={}, count={})",
+				e.guild_id,
+				e.emojis.len()
+			),
+			Event::GuildStickersUpdate(e) => write!(
+				f,
+				"GuildStickersUpdate(guild={}, count={})",
+				e.guild_id,
+				e.stickers.len()
+			),
+			Event::ThreadCreate(e) => write!(f, "ThreadCreate(id={})", e.channel.id),
+			Event::ThreadUpdate(e) => write!(f, "ThreadUpdate(id={})", e.channel.id),
+			Event::ThreadDelete(e) => write!(f, "ThreadDelete(id={})", e.id),
+			Event::ThreadListSync(e) => write!(
+				f,
+				"ThreadListSync(guild={}, count={})",
+				e.guild_id,
+				e.threads.len()
+			),
+			Event::ThreadMemberUpdate(e) => write!(f, "ThreadMemberUpdate(guild={})", e.guild_id),
+			Event::ThreadMembersUpdate(e) => write!(
+				f,
+				"ThreadMembersUpdate(id={}, count={})",
+				e.id, e.member_count
+			),
+			Event::GuildScheduledEventCreate(e) => write!(
+				f,
+				"GuildScheduledEventCreate(guild={}, id={})",
+				e.event.guild_id, e.event.id
+			),
+			Event::GuildScheduledEventUpdate(e) => write!(
+				f,
+				"GuildScheduledEventUpdate(guild={}, id={})",
+				e.event.guild_id, e.event.id
+			),
+			Event::GuildScheduledEventDelete(e) => write!(
+				f,
+				"GuildScheduledEventDelete(guild={}, id={})",
+				e.event.guild_id, e.event.id
+			),
+			Event::GuildScheduledEventUserAdd(e) => write!(
+				f,
+				"GuildScheduledEventUserAdd(id={}, user={})",
+				e.guild_scheduled_event_id, e.user_id
+			),
+			Event::GuildScheduledEventUserRemove(e) => write!(
+				f,
+				"GuildScheduledEventUserRemove(id={}, user={})",
+				e.guild_scheduled_event_id, e.user_id
+			),
+			Event::AutoModerationRuleCreate(e) => write!(
+				f,
+				"AutoModerationRuleCreate(guild={}, id={})",
+				e.rule.guild_id, e.rule.id
+			),
+			Event::AutoModerationRuleUpdate(e) => write!(
+				f,
+				"AutoModerationRuleUpdate(guild={}, id={})",
+				e.rule.guild_id, e.rule.id
+			),
+			Event::AutoModerationRuleDelete(e) => write!(
+				f,
+				"AutoModerationRuleDelete(guild={}, id={})",
+				e.rule.guild_id, e.rule.id
+			),
+			Event::AutoModerationActionExecution(e) => write!(
+				f,
+				"AutoModerationActionExecution(rule={}, user={})",
+				e.rule_id, e.user_id
+			),
+			Event::InviteCreate(e) => {
+				write!(f, "InviteCreate(code={}, channel={})", e.code, e.channel_id)
+			}
+			Event::InviteDelete(e) => {
+				write!(f, "InviteDelete(code={}, channel={})", e.code, e.channel_id)
+			}
+			Event::ChannelPinsUpdate(e) => write!(f, "ChannelPinsUpdate(channel={})", e.channel_id),
+			Event::WebhooksUpdate(e) => write!(f, "WebhooksUpdate(channel={})", e.channel_id),
+			Event::StageInstanceCreate(e) => write!(
+				f,
+				"StageInstanceCreate(channel={}, id={})",
+				e.stage.channel_id, e.stage.id
+			),
+			Event::StageInstanceUpdate(e) => write!(
+				f,
+				"StageInstanceUpdate(channel={}, id={})",
+				e.stage.channel_id, e.stage.id
+			),
+			Event::StageInstanceDelete(e) => write!(
+				f,
+				"StageInstanceDelete(channel={}, id={})",
+				e.stage.channel_id, e.stage.id
+			),
+			Event::IntegrationCreate(e) => write!(
+				f,
+				"IntegrationCreate(guild={}, id={})",
+				e.guild_id, e.integration.id
+			),
+			Event::IntegrationUpdate(e) => write!(
+				f,
+				"IntegrationUpdate(guild={}, id={})",
+				e.guild_id, e.integration.id
+			),
+			Event::IntegrationDelete(e) => {
+				write!(f, "IntegrationDelete(guild={}, id={})", e.guild_id, e.id)
+			}
+			Event::GuildIntegrationsUpdate(e) => {
+				write!(f, "GuildIntegrationsUpdate(guild={})", e.guild_id)
+			}
+			Event::ApplicationCommandPermissionsUpdate(e) => write!(
+				f,
+				"ApplicationCommandPermissionsUpdate(guild={}, id={})",
+				e.permissions.guild_id, e.permissions.id
+			),
+			Event::Unknown(e) => match &e.name {
+				Some(name) if e.op == 0 => write!(f, "Unknown({})", name),
+				_ => write!(f, "Unknown({})", e.op),
+			},
 		}
 	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+// How to proceed after the connection was closed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Recovery {
+	Resume,
+	Identify,
+	Fatal,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CloseCode {
+	UnknownError,
+	UnknownOpcode,
+	DecodeError,
+	NotAuthenticated,
+	AuthenticationFailed,
+	AlreadyAuthenticated,
+	InvalidSequence,
+	RateLimited,
+	SessionTimedOut,
+	InvalidShard,
+	ShardingRequired,
+	InvalidApiVersion,
+	InvalidIntents,
+	DisallowedIntents,
+	Other(u16),
+}
+
+impl CloseCode {
+	pub fn code(&self) -> u16 {
+		match self {
+			CloseCode::UnknownError => 4000,
+			CloseCode::UnknownOpcode => 4001,
+			CloseCode::DecodeError => 4002,
+			CloseCode::NotAuthenticated => 4003,
+			CloseCode::AuthenticationFailed => 4004,
+			CloseCode::AlreadyAuthenticated => 4005,
+			CloseCode::InvalidSequence => 4007,
+			CloseCode::RateLimited => 4008,
+			CloseCode::SessionTimedOut => 4009,
+			CloseCode::InvalidShard => 4010,
+			CloseCode::ShardingRequired => 4011,
+			CloseCode::InvalidApiVersion => 4012,
+			CloseCode::InvalidIntents => 4013,
+			CloseCode::DisallowedIntents => 4014,
+			CloseCode::Other(c) => *c,
+		}
+	}
+
+	pub fn recovery(&self) -> Recovery {
+		match self {
+			CloseCode::NotAuthenticated
+			| CloseCode::InvalidSequence
+			| CloseCode::SessionTimedOut => Recovery::Identify,
+			CloseCode::AuthenticationFailed
+			| CloseCode::InvalidShard
+			| CloseCode::ShardingRequired
+			| CloseCode::InvalidApiVersion
+			| CloseCode::InvalidIntents
+			| CloseCode::DisallowedIntents => Recovery::Fatal,
+			_ => Recovery::Resume,
+		}
+	}
+
+	pub fn is_fatal(&self) -> bool {
+		self.recovery() == Recovery::Fatal
+	}
+}
+
+impl From<u16> for CloseCode {
+	fn from(code: u16) -> Self {
+		match code {
+			4000 => CloseCode::UnknownError,
+			4001 => CloseCode::UnknownOpcode,
+			4002 => CloseCode::DecodeError,
+			4003 => CloseCode::NotAuthenticated,
+			4004 => CloseCode::AuthenticationFailed,
+			4005 => CloseCode::AlreadyAuthenticated,
+			4007 => CloseCode::InvalidSequence,
+			4008 => CloseCode::RateLimited,
+			4009 => CloseCode::SessionTimedOut,
+			4010 => CloseCode::InvalidShard,
+			4011 => CloseCode::ShardingRequired,
+			4012 => CloseCode::InvalidApiVersion,
+			4013 => CloseCode::InvalidIntents,
+			4014 => CloseCode::DisallowedIntents,
+			c => CloseCode::Other(c),
+		}
+	}
+}
+
+impl From<CloseCode> for u16 {
+	fn from(code: CloseCode) -> Self {
+		code.code()
+	}
+}
+
+impl fmt::Display for CloseCode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			CloseCode::Other(c) => write!(f, "Other({})", c),
+			c => write!(f, "{:?}({})", c, c.code()),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Hello {
 	pub heartbeat_interval: u64,
 }
 //
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Ready {
 	pub v: u8,
 	pub user: User,
 	// private_channels
-	#[serde(deserialize_with = "guild_list")]
+	#[serde(with = "guild_list")]
 	pub guilds: HashSet<GuildId>,
 	pub session_id: String,
+	#[serde(default)]
+	pub resume_gateway_url: Option<String>,
 	pub shard: Option<(u8, u8)>,
 	pub application: Application,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct InvalidSession {
 	pub resumable: bool,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct ChannelCreate {
 	pub channel: Channel,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct ChannelUpdate {
 	pub channel: Channel,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct ChannelDelete {
 	pub channel: Channel,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct GuildCreate {
 	pub guild: Guild,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct GuildUpdate {
 	pub guild: Guild,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildDelete {
 	pub id: GuildId,
 	#[serde(default)]
 	pub unavailable: Option<bool>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct MessageCreate {
 	pub message: Message,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MessageUpdate {
 	pub id: MessageId,
 	pub channel_id: ChannelId,
@@ -478,11 +1052,14 @@ pub struct MessageUpdate {
 	pub mention_everyone: bool,
 	#[serde(default)]
 	pub mentions: Vec<User>,
+	// Only set if the embeds changed
+	#[serde(default)]
+	pub embeds: Option<Vec<Embed>>,
 	#[serde(default)]
 	pub pinned: bool,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MessageDelete {
 	pub id: MessageId,
 	pub channel_id: ChannelId,
@@ -490,14 +1067,22 @@ pub struct MessageDelete {
 	pub guild_id: Option<GuildId>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageDeleteBulk {
+	pub ids: Vec<MessageId>,
+	pub channel_id: ChannelId,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildMemberAdd {
 	pub guild_id: GuildId,
 	#[serde(flatten)]
 	pub member: Member,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildMemberUpdate {
 	pub guild_id: GuildId,
 	pub roles: HashSet<RoleId>,
@@ -508,13 +1093,13 @@ pub struct GuildMemberUpdate {
 	pub premium_since: Option<DateTime>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildMemberRemove {
 	pub guild_id: GuildId,
 	pub user: User,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildMembersChunk {
 	pub guild_id: GuildId,
 	pub members: Vec<Member>,
@@ -526,31 +1111,82 @@ pub struct GuildMembersChunk {
 	pub nonce: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildRoleCreate {
 	pub guild_id: GuildId,
 	pub role: Role,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildRoleUpdate {
 	pub guild_id: GuildId,
 	pub role: Role,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildRoleDelete {
 	pub guild_id: GuildId,
 	pub role_id: RoleId,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReactionAdd {
+	pub user_id: UserId,
+	pub channel_id: ChannelId,
+	pub message_id: MessageId,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	#[serde(default)]
+	pub member: Option<Member>,
+	pub emoji: PartialEmoji,
+	#[serde(default)]
+	pub message_author_id: Option<UserId>,
+	#[serde(default)]
+	pub burst: bool,
+	#[serde(default, with = "hex_colors")]
+	pub burst_colors: Vec<Color>,
+	#[serde(default, rename = "type")]
+	pub reaction_type: ReactionType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReactionRemove {
+	pub user_id: UserId,
+	pub channel_id: ChannelId,
+	pub message_id: MessageId,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	pub emoji: PartialEmoji,
+	#[serde(default)]
+	pub burst: bool,
+	#[serde(default, rename = "type")]
+	pub reaction_type: ReactionType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReactionRemoveAll {
+	pub channel_id: ChannelId,
+	pub message_id: MessageId,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReactionRemoveEmoji {
+	pub channel_id: ChannelId,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	pub message_id: MessageId,
+	pub emoji: PartialEmoji,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct InteractionCreate {
 	pub interaction: Interaction,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ApplicationCommandCreate {
 	#[serde(flatten)]
 	pub command: ApplicationCommand,
@@ -558,7 +1194,7 @@ pub struct ApplicationCommandCreate {
 	pub guild_id: Option<GuildId>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ApplicationCommandUpdate {
 	#[serde(flatten)]
 	pub command: ApplicationCommand,
@@ -566,7 +1202,7 @@ pub struct ApplicationCommandUpdate {
 	pub guild_id: Option<GuildId>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ApplicationCommandDelete {
 	#[serde(flatten)]
 	pub command: ApplicationCommand,
@@ -574,13 +1210,13 @@ pub struct ApplicationCommandDelete {
 	pub guild_id: Option<GuildId>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct VoiceStateUpdate {
 	pub voice_state: VoiceState,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VoiceServerUpdate {
 	pub token: String,
 	pub guild_id: GuildId,
@@ -588,35 +1224,979 @@ pub struct VoiceServerUpdate {
 	pub endpoint: Option<String>,
 }
 
-fn guild_list<'de, D>(d: D) -> Result<HashSet<GuildId>, D::Error>
-where
-	D: Deserializer<'de>,
-{
-	#[derive(Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PresenceUpdate {
+	pub user: PresenceUser,
+	pub guild_id: GuildId,
+	pub status: Status,
+	#[serde(default)]
+	pub activities: Vec<Activity>,
+	#[serde(default)]
+	pub client_status: ClientStatus,
+}
+
+// Only the id of the user is guaranteed to be present
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PresenceUser {
+	pub id: UserId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TypingStart {
+	pub channel_id: ChannelId,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	pub user_id: UserId,
+	// Unix time in seconds
+	pub timestamp: u64,
+	#[serde(default)]
+	pub member: Option<Member>,
+}
+
+impl TypingStart {
+	// None if the timestamp is out of range
+	pub fn date_time(&self) -> Option<DateTime> {
+		let secs = i64::try_from(self.timestamp).ok()?;
+		Utc.timestamp_opt(secs, 0).single().map(Into::into)
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct UserUpdate {
+	pub user: User,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildBanAdd {
+	pub guild_id: GuildId,
+	pub user: User,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildBanRemove {
+	pub guild_id: GuildId,
+	pub user: User,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildEmojisUpdate {
+	pub guild_id: GuildId,
+	pub emojis: Vec<Emoji>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildStickersUpdate {
+	pub guild_id: GuildId,
+	pub stickers: Vec<Sticker>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThreadCreate {
+	#[serde(flatten)]
+	pub channel: Channel,
+	#[serde(default)]
+	pub newly_created: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ThreadUpdate {
+	pub channel: Channel,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThreadDelete {
+	pub id: ChannelId,
+	pub guild_id: GuildId,
+	#[serde(default)]
+	pub parent_id: Option<ChannelId>,
+	#[serde(rename = "type")]
+	pub channel_type: ChannelType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThreadListSync {
+	pub guild_id: GuildId,
+	#[serde(default)]
+	pub channel_ids: Vec<ChannelId>,
+	pub threads: Vec<Channel>,
+	pub members: Vec<ThreadMember>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThreadMemberUpdate {
+	#[serde(flatten)]
+	pub member: ThreadMember,
+	pub guild_id: GuildId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThreadMembersUpdate {
+	pub id: ChannelId,
+	pub guild_id: GuildId,
+	pub member_count: u32,
+	#[serde(default)]
+	pub added_members: Vec<ThreadMember>,
+	#[serde(default)]
+	pub removed_member_ids: Vec<UserId>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct GuildScheduledEventCreate {
+	pub event: GuildScheduledEvent,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct GuildScheduledEventUpdate {
+	pub event: GuildScheduledEvent,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct GuildScheduledEventDelete {
+	pub event: GuildScheduledEvent,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildScheduledEventUserAdd {
+	pub guild_scheduled_event_id: Snowflake,
+	pub user_id: UserId,
+	pub guild_id: GuildId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildScheduledEventUserRemove {
+	pub guild_scheduled_event_id: Snowflake,
+	pub user_id: UserId,
+	pub guild_id: GuildId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AutoModerationRuleCreate {
+	pub rule: AutoModerationRule,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AutoModerationRuleUpdate {
+	pub rule: AutoModerationRule,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AutoModerationRuleDelete {
+	pub rule: AutoModerationRule,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AutoModerationActionExecution {
+	pub guild_id: GuildId,
+	pub action: AutoModerationAction,
+	pub rule_id: Snowflake,
+	pub rule_trigger_type: TriggerType,
+	pub user_id: UserId,
+	#[serde(default)]
+	pub channel_id: Option<ChannelId>,
+	// Missing if the message was blocked
+	#[serde(default)]
+	pub message_id: Option<MessageId>,
+	#[serde(default)]
+	pub alert_system_message_id: Option<MessageId>,
+	// Empty without the MESSAGE_CONTENT intent
+	#[serde(default)]
+	pub content: String,
+	pub matched_keyword: Option<String>,
+	#[serde(default)]
+	pub matched_content: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InviteCreate {
+	pub channel_id: ChannelId,
+	pub code: String,
+	pub created_at: DateTime,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	#[serde(default)]
+	pub inviter: Option<User>,
+	pub max_age: u32,
+	pub max_uses: u32,
+	#[serde(default)]
+	pub target_type: Option<InviteTargetType>,
+	#[serde(default)]
+	pub target_user: Option<User>,
+	// target_application
+	pub temporary: bool,
+	pub uses: u32,
+	#[serde(default)]
+	pub expires_at: Option<DateTime>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InviteDelete {
+	pub channel_id: ChannelId,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	pub code: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChannelPinsUpdate {
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	pub channel_id: ChannelId,
+	// None once the last pin has been removed
+	#[serde(default)]
+	pub last_pin_timestamp: Option<DateTime>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebhooksUpdate {
+	pub guild_id: GuildId,
+	pub channel_id: ChannelId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct StageInstanceCreate {
+	pub stage: StageInstance,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct StageInstanceUpdate {
+	pub stage: StageInstance,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct StageInstanceDelete {
+	pub stage: StageInstance,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IntegrationCreate {
+	#[serde(flatten)]
+	pub integration: Integration,
+	pub guild_id: GuildId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IntegrationUpdate {
+	#[serde(flatten)]
+	pub integration: Integration,
+	pub guild_id: GuildId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IntegrationDelete {
+	pub id: Snowflake,
+	pub guild_id: GuildId,
+	#[serde(default)]
+	pub application_id: Option<ApplicationId>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildIntegrationsUpdate {
+	pub guild_id: GuildId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ApplicationCommandPermissionsUpdate {
+	pub permissions: GuildApplicationCommandPermissions,
+}
+
+// Event the crate doesn't model, with its raw payload so it can be forwarded or replayed
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UnknownEvent {
+	pub op: u8,
+	// Only set for dispatch events
+	pub name: Option<String>,
+	pub data: serde_json::Value,
+}
+
+mod guild_list {
+	use super::*;
+
+	#[derive(Deserialize, Serialize)]
 	struct Guild {
 		id: GuildId,
+		#[serde(default)]
+		unavailable: bool,
+	}
+
+	pub fn serialize<S>(guilds: &HashSet<GuildId>, s: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut seq = s.serialize_seq(Some(guilds.len()))?;
+		for &id in guilds {
+			seq.serialize_element(&Guild {
+				id,
+				unavailable: true,
+			})?;
+		}
+		seq.end()
+	}
+
+	pub fn deserialize<'de, D>(d: D) -> Result<HashSet<GuildId>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct GuildsVisitor;
+
+		impl<'de> Visitor<'de> for GuildsVisitor {
+			type Value = HashSet<GuildId>;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("list of guilds")
+			}
+
+			fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+			where
+				V: SeqAccess<'de>,
+			{
+				let mut list = HashSet::new();
+				while let Some(guild) = seq.next_element::<Guild>()? {
+					list.insert(guild.id);
+				}
+				Ok(list)
+			}
+		}
+
+		d.deserialize_seq(GuildsVisitor)
 	}
+}
+
+mod hex_colors {
+	use super::*;
 
-	struct GuildsVisitor;
+	pub fn serialize<S>(colors: &[Color], s: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		s.collect_seq(colors.iter().map(|c| c.to_string()))
+	}
 
-	impl<'de> Visitor<'de> for GuildsVisitor {
-		type Value = HashSet<GuildId>;
+	pub fn deserialize<'de, D>(d: D) -> Result<Vec<Color>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let colors = Vec::<String>::deserialize(d)?;
+		colors
+			.iter()
+			.map(|c| {
+				c.parse()
+					.map_err(|_| de::Error::invalid_value(de::Unexpected::Str(c), &"hex color"))
+			})
+			.collect()
+	}
+}
 
-		fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-			formatter.write_str("list of guilds")
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const USER: &str = r#"{"id":"1","username":"user","discriminator":"0","avatar":null}"#;
+	const ROLE: &str = r#"{"id":"2","name":"mod","color":0,"hoist":false,"position":1,"permissions":"8192","managed":false,"mentionable":true}"#;
+	const CHANNEL: &str = r#"{"id":"4","type":0,"guild_id":"1","position":0,"permission_overwrites":[{"id":"2","type":0,"allow":"2048","deny":"0"}],"name":"general"}"#;
+	const EMOJI: &str = r#"{"id":"8","name":"blob","roles":["2"],"require_colons":true,"managed":false,"animated":true,"available":true}"#;
+	const STICKER: &str = r#"{"id":"9","name":"wave","description":null,"tags":"wave","type":2,"format_type":1,"available":true,"guild_id":"1"}"#;
+	const FORUM: &str = r#"{"id":"11","type":15,"guild_id":"1","name":"questions","flags":16,"available_tags":[{"id":"12","name":"solved","moderated":true,"emoji_id":null,"emoji_name":"✅"}],"default_reaction_emoji":{"emoji_id":"8","emoji_name":null},"default_thread_rate_limit_per_user":60,"default_sort_order":1,"default_forum_layout":2}"#;
+	const THREAD: &str = r#"{"id":"10","type":11,"guild_id":"1","name":"help","rate_limit_per_user":0,"owner_id":"1","parent_id":"4","thread_metadata":{"archived":false,"auto_archive_duration":1440,"archive_timestamp":"2023-01-01T00:00:00+00:00","locked":false,"create_timestamp":"2023-01-01T00:00:00+00:00"},"member_count":1,"message_count":3,"applied_tags":["12"],"member":{"id":"10","user_id":"1","join_timestamp":"2023-01-01T00:00:00+00:00","flags":0}}"#;
+	const THREAD_MEMBER: &str =
+		r#"{"id":"10","user_id":"1","join_timestamp":"2023-01-01T00:00:00+00:00","flags":1}"#;
+	const SCHEDULED_EVENT: &str = r#"{"id":"13","guild_id":"1","channel_id":null,"creator_id":"1","name":"Weekly","description":"Meetup","scheduled_start_time":"2023-01-02T18:00:00+00:00","scheduled_end_time":"2023-01-02T20:00:00+00:00","privacy_level":2,"status":1,"entity_type":3,"entity_id":null,"entity_metadata":{"location":"Park"},"user_count":3,"image":null,"recurrence_rule":{"start":"2023-01-02T18:00:00+00:00","end":null,"frequency":2,"interval":1,"by_weekday":[0],"by_n_weekday":null,"by_month":null,"by_month_day":null,"by_year_day":null,"count":null}}"#;
+	const AUTOMOD_RULE: &str = r#"{"id":"14","guild_id":"1","name":"Keywords","creator_id":"1","event_type":1,"trigger_type":1,"trigger_metadata":{"keyword_filter":["bad*"],"regex_patterns":[],"allow_list":["badge"]},"actions":[{"type":1,"metadata":{"custom_message":"No"}},{"type":2,"metadata":{"channel_id":"3"}}],"enabled":true,"exempt_roles":["2"],"exempt_channels":[]}"#;
+	const COMMAND: &str =
+		r#""id":"5","application_id":"6","name":"ping","description":"Ping","options":[]"#;
+
+	fn member() -> String {
+		format!(
+			r#"{{"user":{},"roles":["2"],"joined_at":"2023-01-01T00:00:00+00:00","deaf":false,"mute":false}}"#,
+			USER
+		)
+	}
+
+	fn guild() -> String {
+		format!(
+			r#"{{"id":"1","name":"guild","icon":null,"splash":null,"discovery_splash":null,"owner_id":"1","region":"","afk_channel_id":null,"afk_timeout":300,"verification_level":0,"default_message_notifications":0,"explicit_content_filter":0,"roles":[{}],"emojis":[{}],"stickers":[{}],"mfa_level":0,"application_id":null,"system_channel_id":null,"system_channel_flags":0,"rules_channel_id":null,"members":[{}],"channels":[{}],"vanity_url_code":null,"description":null,"banner":null,"premium_tier":0,"preferred_locale":"en-US","public_updates_channel_id":null}}"#,
+			ROLE,
+			EMOJI,
+			STICKER,
+			member(),
+			CHANNEL
+		)
+	}
+
+	fn message() -> String {
+		format!(
+			r#"{{"id":"3","channel_id":"4","guild_id":"1","author":{},"member":{},"content":"hi","timestamp":"2023-01-01T00:00:00+00:00","edited_timestamp":null,"attachments":[{{"id":"7","filename":"voice-message.ogg","content_type":"audio/ogg","size":1024,"url":"https://cdn.discordapp.com/a","proxy_url":"https://media.discordapp.net/a","duration_secs":2.5,"waveform":"AAAA"}}],"embeds":[{{"type":"rich","title":"t","image":{{"url":"https://example.com/a.png","proxy_url":"https://media.discordapp.net/a.png","height":1080,"width":1920}}}}],"reactions":[{{"count":1,"me":false,"emoji":{{"name":"👍"}}}}],"type":0}}"#,
+			USER,
+			member()
+		)
+	}
+
+	fn voice_state() -> String {
+		format!(
+			r#"{{"guild_id":"1","channel_id":"4","user_id":"1","member":{},"session_id":"s","deaf":false,"mute":false,"self_deaf":false,"self_mute":true,"self_video":false,"suppress":false}}"#,
+			member()
+		)
+	}
+
+	fn dispatch(t: &str, d: &str) -> String {
+		format!(r#"{{"t":"{}","s":2,"op":0,"d":{}}}"#, t, d)
+	}
+
+	fn fixtures() -> Vec<(&'static str, String)> {
+		vec![
+			(
+				"Hello",
+				r#"{"op":10,"d":{"heartbeat_interval":41250}}"#.into(),
+			),
+			(
+				"Ready",
+				dispatch(
+					"READY",
+					&format!(
+						r#"{{"v":10,"user":{},"guilds":[{{"id":"1","unavailable":true}}],"session_id":"abc","resume_gateway_url":"wss://gateway","shard":[0,1],"application":{{"id":"6","flags":0}}}}"#,
+						USER
+					),
+				),
+			),
+			("Resumed", dispatch("RESUMED", "null")),
+			("Reconnect", r#"{"op":7,"d":null}"#.into()),
+			("InvalidSession", r#"{"op":9,"d":false}"#.into()),
+			("HeartbeatRequest", r#"{"op":1,"d":null}"#.into()),
+			("HeartbeatAck", r#"{"op":11,"d":null}"#.into()),
+			("GuildCreate", dispatch("GUILD_CREATE", &guild())),
+			("GuildUpdate", dispatch("GUILD_UPDATE", &guild())),
+			(
+				"GuildDelete",
+				dispatch("GUILD_DELETE", r#"{"id":"1","unavailable":true}"#),
+			),
+			("MessageCreate", dispatch("MESSAGE_CREATE", &message())),
+			(
+				"MessageCreate",
+				dispatch(
+					"MESSAGE_CREATE",
+					&format!(
+						r#"{{"id":"18","channel_id":"4","guild_id":"1","author":{},"content":"hello","timestamp":"2023-01-01T00:01:00+00:00","edited_timestamp":null,"type":19,"message_reference":{{"type":0,"message_id":"3","channel_id":"4","guild_id":"1"}},"referenced_message":{}}}"#,
+						USER,
+						message()
+					),
+				),
+			),
+			(
+				"MessageCreate",
+				dispatch(
+					"MESSAGE_CREATE",
+					&format!(
+						r#"{{"id":"19","channel_id":"4","guild_id":"1","author":{},"content":"","timestamp":"2023-01-01T00:02:00+00:00","edited_timestamp":null,"type":0,"message_reference":{{"type":1,"message_id":"3","channel_id":"4","guild_id":"1"}},"message_snapshots":[{{"message":{{"type":0,"content":"hi","embeds":[],"attachments":[],"timestamp":"2023-01-01T00:00:00+00:00","edited_timestamp":null,"flags":0,"mentions":[],"mention_roles":[],"components":[]}}}}]}}"#,
+						USER
+					),
+				),
+			),
+			(
+				"MessageUpdate",
+				dispatch(
+					"MESSAGE_UPDATE",
+					r#"{"id":"3","channel_id":"4","guild_id":"1","content":"edit","edited_timestamp":"2023-01-01T00:01:00+00:00","embeds":[{"description":"d"}]}"#,
+				),
+			),
+			(
+				"MessageDelete",
+				dispatch(
+					"MESSAGE_DELETE",
+					r#"{"id":"3","channel_id":"4","guild_id":"1"}"#,
+				),
+			),
+			(
+				"GuildMemberAdd",
+				dispatch(
+					"GUILD_MEMBER_ADD",
+					&member().replacen('{', r#"{"guild_id":"1","#, 1),
+				),
+			),
+			(
+				"GuildMemberUpdate",
+				dispatch(
+					"GUILD_MEMBER_UPDATE",
+					&format!(
+						r#"{{"guild_id":"1","roles":["2"],"user":{},"nick":"nick"}}"#,
+						USER
+					),
+				),
+			),
+			(
+				"GuildMemberRemove",
+				dispatch(
+					"GUILD_MEMBER_REMOVE",
+					&format!(r#"{{"guild_id":"1","user":{}}}"#, USER),
+				),
+			),
+			(
+				"GuildMembersChunk",
+				dispatch(
+					"GUILD_MEMBERS_CHUNK",
+					&format!(
+						r#"{{"guild_id":"1","members":[{}],"chunk_index":0,"chunk_count":1,"nonce":"n"}}"#,
+						member()
+					),
+				),
+			),
+			(
+				"GuildRoleCreate",
+				dispatch(
+					"GUILD_ROLE_CREATE",
+					&format!(r#"{{"guild_id":"1","role":{}}}"#, ROLE),
+				),
+			),
+			(
+				"GuildRoleUpdate",
+				dispatch(
+					"GUILD_ROLE_UPDATE",
+					&format!(r#"{{"guild_id":"1","role":{}}}"#, ROLE),
+				),
+			),
+			(
+				"GuildRoleDelete",
+				dispatch("GUILD_ROLE_DELETE", r#"{"guild_id":"1","role_id":"2"}"#),
+			),
+			("ChannelCreate", dispatch("CHANNEL_CREATE", CHANNEL)),
+			("ChannelCreate", dispatch("CHANNEL_CREATE", FORUM)),
+			("ChannelUpdate", dispatch("CHANNEL_UPDATE", CHANNEL)),
+			("ChannelDelete", dispatch("CHANNEL_DELETE", CHANNEL)),
+			(
+				"MessageReactionAdd",
+				dispatch(
+					"MESSAGE_REACTION_ADD",
+					&format!(
+						r##"{{"user_id":"1","channel_id":"4","message_id":"3","guild_id":"1","member":{},"emoji":{{"id":"8","name":"blob","animated":false}},"burst":true,"burst_colors":["#ff0000"],"type":1}}"##,
+						member()
+					),
+				),
+			),
+			(
+				"MessageReactionRemove",
+				dispatch(
+					"MESSAGE_REACTION_REMOVE",
+					r#"{"user_id":"1","channel_id":"4","message_id":"3","guild_id":"1","emoji":{"name":"👍"},"burst":false,"type":0}"#,
+				),
+			),
+			(
+				"MessageReactionRemoveAll",
+				dispatch(
+					"MESSAGE_REACTION_REMOVE_ALL",
+					r#"{"channel_id":"4","message_id":"3","guild_id":"1"}"#,
+				),
+			),
+			(
+				"MessageReactionRemoveEmoji",
+				dispatch(
+					"MESSAGE_REACTION_REMOVE_EMOJI",
+					r#"{"channel_id":"4","message_id":"3","guild_id":"1","emoji":{"name":"👍"}}"#,
+				),
+			),
+			(
+				"ApplicationCommandCreate",
+				dispatch(
+					"APPLICATION_COMMAND_CREATE",
+					&format!(r#"{{{},"guild_id":"1"}}"#, COMMAND),
+				),
+			),
+			(
+				"ApplicationCommandUpdate",
+				dispatch(
+					"APPLICATION_COMMAND_UPDATE",
+					&format!(r#"{{{},"guild_id":"1"}}"#, COMMAND),
+				),
+			),
+			(
+				"ApplicationCommandDelete",
+				dispatch("APPLICATION_COMMAND_DELETE", &format!(r#"{{{}}}"#, COMMAND)),
+			),
+			(
+				"InteractionCreate",
+				dispatch(
+					"INTERACTION_CREATE",
+					&format!(
+						r#"{{"id":"7","application_id":"6","type":2,"data":{{"id":"5","name":"ping","options":[{{"name":"n","value":"v"}}]}},"guild_id":"1","channel_id":"4","member":{},"token":"tok","version":1,"app_permissions":"2048"}}"#,
+						member()
+					),
+				),
+			),
+			(
+				"VoiceStateUpdate",
+				dispatch("VOICE_STATE_UPDATE", &voice_state()),
+			),
+			(
+				"VoiceServerUpdate",
+				dispatch(
+					"VOICE_SERVER_UPDATE",
+					r#"{"token":"tok","guild_id":"1","endpoint":"voice.discord.media"}"#,
+				),
+			),
+			(
+				"PresenceUpdate",
+				dispatch(
+					"PRESENCE_UPDATE",
+					r#"{"user":{"id":"1"},"guild_id":"1","status":"dnd","activities":[{"name":"Custom Status","type":4,"state":"busy","emoji":{"name":"🔥"},"created_at":1700000000000},{"name":"Game","type":0,"timestamps":{"start":1700000000000},"assets":{"large_image":"img","large_text":"text"},"party":{"id":"p","size":[1,4]},"buttons":["Join"],"flags":3}],"client_status":{"desktop":"dnd"}}"#,
+				),
+			),
+			(
+				"TypingStart",
+				dispatch(
+					"TYPING_START",
+					&format!(
+						r#"{{"channel_id":"4","guild_id":"1","user_id":"1","timestamp":1700000000,"member":{}}}"#,
+						member()
+					),
+				),
+			),
+			("UserUpdate", dispatch("USER_UPDATE", USER)),
+			(
+				"GuildBanAdd",
+				dispatch(
+					"GUILD_BAN_ADD",
+					&format!(r#"{{"guild_id":"1","user":{}}}"#, USER),
+				),
+			),
+			(
+				"GuildBanRemove",
+				dispatch(
+					"GUILD_BAN_REMOVE",
+					&format!(r#"{{"guild_id":"1","user":{}}}"#, USER),
+				),
+			),
+			(
+				"GuildEmojisUpdate",
+				dispatch(
+					"GUILD_EMOJIS_UPDATE",
+					&format!(r#"{{"guild_id":"1","emojis":[{}]}}"#, EMOJI),
+				),
+			),
+			(
+				"GuildStickersUpdate",
+				dispatch(
+					"GUILD_STICKERS_UPDATE",
+					&format!(r#"{{"guild_id":"1","stickers":[{}]}}"#, STICKER),
+				),
+			),
+			(
+				"ThreadCreate",
+				dispatch(
+					"THREAD_CREATE",
+					&THREAD.replacen('{', r#"{"newly_created":true,"#, 1),
+				),
+			),
+			("ThreadUpdate", dispatch("THREAD_UPDATE", THREAD)),
+			(
+				"ThreadDelete",
+				dispatch(
+					"THREAD_DELETE",
+					r#"{"id":"10","guild_id":"1","parent_id":"4","type":11}"#,
+				),
+			),
+			(
+				"ThreadListSync",
+				dispatch(
+					"THREAD_LIST_SYNC",
+					&format!(
+						r#"{{"guild_id":"1","channel_ids":["4"],"threads":[{}],"members":[{}]}}"#,
+						THREAD, THREAD_MEMBER
+					),
+				),
+			),
+			(
+				"ThreadMemberUpdate",
+				dispatch(
+					"THREAD_MEMBER_UPDATE",
+					&THREAD_MEMBER.replacen('{', r#"{"guild_id":"1","#, 1),
+				),
+			),
+			(
+				"ThreadMembersUpdate",
+				dispatch(
+					"THREAD_MEMBERS_UPDATE",
+					&format!(
+						r#"{{"id":"10","guild_id":"1","member_count":2,"added_members":[{}],"removed_member_ids":["11"]}}"#,
+						THREAD_MEMBER
+					),
+				),
+			),
+			(
+				"GuildScheduledEventCreate",
+				dispatch("GUILD_SCHEDULED_EVENT_CREATE", SCHEDULED_EVENT),
+			),
+			(
+				"GuildScheduledEventUpdate",
+				dispatch("GUILD_SCHEDULED_EVENT_UPDATE", SCHEDULED_EVENT),
+			),
+			(
+				"GuildScheduledEventDelete",
+				dispatch("GUILD_SCHEDULED_EVENT_DELETE", SCHEDULED_EVENT),
+			),
+			(
+				"GuildScheduledEventUserAdd",
+				dispatch(
+					"GUILD_SCHEDULED_EVENT_USER_ADD",
+					r#"{"guild_scheduled_event_id":"13","user_id":"1","guild_id":"1"}"#,
+				),
+			),
+			(
+				"GuildScheduledEventUserRemove",
+				dispatch(
+					"GUILD_SCHEDULED_EVENT_USER_REMOVE",
+					r#"{"guild_scheduled_event_id":"13","user_id":"1","guild_id":"1"}"#,
+				),
+			),
+			(
+				"AutoModerationRuleCreate",
+				dispatch("AUTO_MODERATION_RULE_CREATE", AUTOMOD_RULE),
+			),
+			(
+				"AutoModerationRuleUpdate",
+				dispatch("AUTO_MODERATION_RULE_UPDATE", AUTOMOD_RULE),
+			),
+			(
+				"AutoModerationRuleDelete",
+				dispatch("AUTO_MODERATION_RULE_DELETE", AUTOMOD_RULE),
+			),
+			(
+				"AutoModerationActionExecution",
+				dispatch(
+					"AUTO_MODERATION_ACTION_EXECUTION",
+					r#"{"guild_id":"1","action":{"type":3,"metadata":{"duration_seconds":60}},"rule_id":"14","rule_trigger_type":1,"user_id":"1","channel_id":"3","message_id":"5","alert_system_message_id":null,"content":"bad word","matched_keyword":"bad*","matched_content":"bad"}"#,
+				),
+			),
+			(
+				"InviteCreate",
+				dispatch(
+					"INVITE_CREATE",
+					&format!(
+						r#"{{"channel_id":"3","code":"abc","created_at":"2023-01-01T00:00:00+00:00","guild_id":"1","inviter":{},"max_age":86400,"max_uses":0,"target_type":null,"target_user":null,"temporary":false,"uses":0,"expires_at":"2023-01-02T00:00:00+00:00"}}"#,
+						USER
+					),
+				),
+			),
+			(
+				"InviteDelete",
+				dispatch(
+					"INVITE_DELETE",
+					r#"{"channel_id":"3","guild_id":"1","code":"abc"}"#,
+				),
+			),
+			(
+				"MessageDeleteBulk",
+				dispatch(
+					"MESSAGE_DELETE_BULK",
+					r#"{"ids":["5","6"],"channel_id":"4","guild_id":"1"}"#,
+				),
+			),
+			(
+				"ChannelPinsUpdate",
+				dispatch(
+					"CHANNEL_PINS_UPDATE",
+					r#"{"guild_id":"1","channel_id":"4","last_pin_timestamp":"2023-01-01T00:00:00+00:00"}"#,
+				),
+			),
+			(
+				"WebhooksUpdate",
+				dispatch("WEBHOOKS_UPDATE", r#"{"guild_id":"1","channel_id":"4"}"#),
+			),
+			(
+				"StageInstanceCreate",
+				dispatch(
+					"STAGE_INSTANCE_CREATE",
+					r#"{"id":"15","guild_id":"1","channel_id":"16","topic":"Town hall","privacy_level":2,"discoverable_disabled":false,"guild_scheduled_event_id":null}"#,
+				),
+			),
+			(
+				"StageInstanceUpdate",
+				dispatch(
+					"STAGE_INSTANCE_UPDATE",
+					r#"{"id":"15","guild_id":"1","channel_id":"16","topic":"Town hall","privacy_level":2,"discoverable_disabled":false,"guild_scheduled_event_id":null}"#,
+				),
+			),
+			(
+				"StageInstanceDelete",
+				dispatch(
+					"STAGE_INSTANCE_DELETE",
+					r#"{"id":"15","guild_id":"1","channel_id":"16","topic":"Town hall","privacy_level":2,"discoverable_disabled":false,"guild_scheduled_event_id":null}"#,
+				),
+			),
+			(
+				"IntegrationCreate",
+				dispatch(
+					"INTEGRATION_CREATE",
+					r#"{"id":"17","name":"bot","type":"discord","enabled":true,"account":{"id":"2","name":"bot"},"application":{"id":"2","name":"bot","icon":null,"description":"","bot":null},"scopes":["bot","applications.commands"],"user":null,"guild_id":"1"}"#,
+				),
+			),
+			(
+				"IntegrationUpdate",
+				dispatch(
+					"INTEGRATION_UPDATE",
+					r#"{"id":"17","name":"bot","type":"discord","enabled":true,"account":{"id":"2","name":"bot"},"application":{"id":"2","name":"bot","icon":null,"description":"","bot":null},"scopes":["bot","applications.commands"],"user":null,"guild_id":"1"}"#,
+				),
+			),
+			(
+				"IntegrationDelete",
+				dispatch(
+					"INTEGRATION_DELETE",
+					r#"{"id":"17","guild_id":"1","application_id":"2"}"#,
+				),
+			),
+			(
+				"GuildIntegrationsUpdate",
+				dispatch("GUILD_INTEGRATIONS_UPDATE", r#"{"guild_id":"1"}"#),
+			),
+			(
+				"ApplicationCommandPermissionsUpdate",
+				dispatch(
+					"APPLICATION_COMMAND_PERMISSIONS_UPDATE",
+					r#"{"id":"9","application_id":"2","guild_id":"1","permissions":[{"id":"2","type":1,"permission":true},{"id":"0","type":3,"permission":false}]}"#,
+				),
+			),
+			("Unknown(SOMETHING_NEW)", dispatch("SOMETHING_NEW", "{}")),
+			("Unknown(42)", r#"{"op":42,"d":null}"#.into()),
+			(
+				"Unknown(SOMETHING_ELSE)",
+				dispatch(
+					"SOMETHING_ELSE",
+					r#"{"id":"1","nested":{"list":[1,2.5,null]}}"#,
+				),
+			),
+		]
+	}
+
+	fn is_default(value: &serde_json::Value) -> bool {
+		use serde_json::Value;
+		match value {
+			Value::Null => true,
+			Value::Bool(b) => !b,
+			Value::Number(n) => n.as_f64() == Some(0.0),
+			Value::String(s) => s.is_empty(),
+			Value::Array(a) => a.is_empty(),
+			Value::Object(o) => o.values().all(is_default),
 		}
+	}
 
-		fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
-		where
-			V: SeqAccess<'de>,
-		{
-			let mut list = HashSet::new();
-			while let Some(guild) = seq.next_element::<Guild>()? {
-				list.insert(guild.id);
+	// Compare event data, allowing fields the original omitted to be serialized with their default
+	fn assert_same_data(value: &serde_json::Value, original: &serde_json::Value, name: &str) {
+		use serde_json::Value;
+		match (value, original) {
+			(Value::Object(value), Value::Object(original)) => {
+				for (key, v) in original {
+					let field = format!("{}.{}", name, key);
+					match value.get(key) {
+						Some(value) => assert_same_data(value, v, &field),
+						None => assert!(v.is_null(), "{} was dropped", field),
+					}
+				}
+				for (key, v) in value {
+					if !original.contains_key(key) {
+						assert!(is_default(v), "{}.{} was added as {}", name, key, v);
+					}
+				}
+			}
+			(Value::Array(value), Value::Array(original)) => {
+				assert_eq!(value.len(), original.len(), "{}", name);
+				for (i, (value, original)) in value.iter().zip(original).enumerate() {
+					assert_same_data(value, original, &format!("{}[{}]", name, i));
+				}
+			}
+			(Value::Number(value), Value::Number(original)) => {
+				assert_eq!(value.as_f64(), original.as_f64(), "{}", name)
 			}
-			Ok(list)
+			(value, original) => assert_eq!(value, original, "{}", name),
 		}
 	}
 
-	d.deserialize_seq(GuildsVisitor)
+	#[test]
+	fn round_trip() {
+		for (name, json) in fixtures() {
+			let payload: Payload = serde_json::from_str(&json).unwrap();
+			assert!(
+				payload.event.to_string().starts_with(name),
+				"{} decoded as {}",
+				name,
+				payload
+			);
+
+			let original: serde_json::Value = serde_json::from_str(&json).unwrap();
+			let value = serde_json::to_value(&payload).unwrap();
+			assert_eq!(value["op"], original["op"], "{}", name);
+			assert_eq!(value["s"], original.get("s").cloned().unwrap_or_default());
+			assert_eq!(value["t"], original.get("t").cloned().unwrap_or_default());
+			assert_same_data(
+				&value["d"],
+				original.get("d").unwrap_or(&serde_json::Value::Null),
+				name,
+			);
+
+			let json = serde_json::to_string(&payload).unwrap();
+			let again: Payload = serde_json::from_str(&json).unwrap();
+			assert_eq!(
+				serde_json::to_value(&again).unwrap(),
+				value,
+				"{} does not round-trip",
+				name
+			);
+		}
+	}
+
+	#[test]
+	fn typing_start() {
+		let json = r#"{"channel_id":"1","user_id":"2","timestamp":1672531200}"#;
+		let mut typing: TypingStart = serde_json::from_str(json).unwrap();
+		assert_eq!(typing.date_time().unwrap().timestamp(), 1672531200);
+		typing.timestamp = u64::MAX;
+		assert!(typing.date_time().is_none());
+	}
+
+	#[test]
+	fn message_reaction_add() {
+		let json = r##"{"t":"MESSAGE_REACTION_ADD","s":5,"op":0,"d":{"user_id":"1","type":1,"message_id":"2","message_author_id":"3","emoji":{"name":"blob","id":"4","animated":true},"channel_id":"5","burst_colors":["#ff0000","#00ff00"],"burst":true,"guild_id":"6"}}"##;
+		let payload: Payload = serde_json::from_str(json).unwrap();
+		let event = match payload.event {
+			Event::MessageReactionAdd(e) => e,
+			e => panic!("unexpected event {}", e),
+		};
+		assert_eq!(event.guild_id, Some(6.into()));
+		assert_eq!(event.reaction_type, ReactionType::Burst);
+		assert_eq!(
+			event.burst_colors,
+			vec![Color::from_rgb(255, 0, 0), Color::from_rgb(0, 255, 0)]
+		);
+		assert_eq!(format!("{}", event.emoji), "<a:blob:4>");
+	}
+
+	#[test]
+	fn close_code() {
+		for code in 4000..=4014 {
+			assert_eq!(CloseCode::from(code).code(), code);
+		}
+		assert_eq!(CloseCode::from(4006), CloseCode::Other(4006));
+		assert_eq!(CloseCode::from(4000).recovery(), Recovery::Resume);
+		assert_eq!(CloseCode::from(4009).recovery(), Recovery::Identify);
+		assert!(CloseCode::from(4014).is_fatal());
+		assert_eq!(
+			CloseCode::DisallowedIntents.to_string(),
+			"DisallowedIntents(4014)"
+		);
+	}
+
+	#[test]
+	fn reconnect() {
+		let json = r#"{"op":7,"d":null}"#;
+		let payload: Payload = serde_json::from_str(json).unwrap();
+		assert!(payload.event.is_reconnect());
+		assert_eq!(payload.sequence, None);
+	}
 }
@@ -1,11 +1,14 @@
 pub use command::Command;
-pub use event::{Event, Payload};
+pub use event::{CloseCode, Event, Payload, Recovery};
+pub use session::GatewaySession;
 pub use types::*;
 
 mod bitflags;
 pub mod command;
 pub mod event;
+pub mod markup;
 pub mod request;
+pub mod session;
 mod types;
 pub mod voice;
 
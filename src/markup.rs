@@ -0,0 +1,262 @@
+use crate::{ChannelId, DateTime, PartialEmoji, RoleId, Snowflake, UserId};
+use chrono::{TimeZone, Utc};
+use std::fmt;
+use std::ops::Range;
+
+// Mention or tag found in message content, `span` is its byte range in the content
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Token {
+	pub kind: TokenKind,
+	pub span: Range<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TokenKind {
+	User(UserId),
+	Role(RoleId),
+	Channel(ChannelId),
+	Emoji(PartialEmoji),
+	// Name includes the subcommand (group), separated by spaces
+	Command {
+		name: String,
+		id: Snowflake,
+	},
+	Timestamp {
+		time: DateTime,
+		style: Option<TimestampStyle>,
+	},
+	Everyone,
+	Here,
+}
+
+impl fmt::Display for TokenKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			TokenKind::User(id) => write!(f, "<@{}>", id),
+			TokenKind::Role(id) => write!(f, "<@&{}>", id),
+			TokenKind::Channel(id) => write!(f, "<#{}>", id),
+			TokenKind::Emoji(emoji) => write!(f, "{}", emoji),
+			TokenKind::Command { name, id } => write!(f, "</{}:{}>", name, id),
+			TokenKind::Timestamp { time, style } => {
+				write!(f, "<t:{}", time.timestamp())?;
+				if let Some(style) = style {
+					write!(f, ":{}", style.as_char())?;
+				}
+				write!(f, ">")
+			}
+			TokenKind::Everyone => write!(f, "@everyone"),
+			TokenKind::Here => write!(f, "@here"),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimestampStyle {
+	ShortTime,
+	LongTime,
+	ShortDate,
+	LongDate,
+	ShortDateTime,
+	LongDateTime,
+	Relative,
+}
+
+impl TimestampStyle {
+	pub fn from_char(c: char) -> Option<Self> {
+		let style = match c {
+			't' => TimestampStyle::ShortTime,
+			'T' => TimestampStyle::LongTime,
+			'd' => TimestampStyle::ShortDate,
+			'D' => TimestampStyle::LongDate,
+			'f' => TimestampStyle::ShortDateTime,
+			'F' => TimestampStyle::LongDateTime,
+			'R' => TimestampStyle::Relative,
+			_ => return None,
+		};
+		Some(style)
+	}
+
+	pub fn as_char(self) -> char {
+		match self {
+			TimestampStyle::ShortTime => 't',
+			TimestampStyle::LongTime => 'T',
+			TimestampStyle::ShortDate => 'd',
+			TimestampStyle::LongDate => 'D',
+			TimestampStyle::ShortDateTime => 'f',
+			TimestampStyle::LongDateTime => 'F',
+			TimestampStyle::Relative => 'R',
+		}
+	}
+}
+
+// Iterate over the tokens in `content`, in order of appearance
+pub fn tokens(content: &str) -> Tokens<'_> {
+	Tokens { content, pos: 0 }
+}
+
+#[derive(Clone, Debug)]
+pub struct Tokens<'a> {
+	content: &'a str,
+	pos: usize,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+	type Item = Token;
+
+	fn next(&mut self) -> Option<Token> {
+		while let Some(offset) = self.content[self.pos..].find(['<', '@']) {
+			let start = self.pos + offset;
+			let rest = &self.content[start..];
+			// Both '<' and '@' are one byte, so this is always a char boundary
+			self.pos = start + 1;
+
+			let (kind, len) = if rest.starts_with('@') {
+				if rest.starts_with("@everyone") {
+					(TokenKind::Everyone, "@everyone".len())
+				} else if rest.starts_with("@here") {
+					(TokenKind::Here, "@here".len())
+				} else {
+					continue;
+				}
+			} else {
+				let end = match rest.find('>') {
+					Some(end) => end,
+					None => continue,
+				};
+				match parse_tag(&rest[1..end]) {
+					Some(kind) => (kind, end + 1),
+					None => continue,
+				}
+			};
+
+			self.pos = start + len;
+			return Some(Token {
+				kind,
+				span: start..start + len,
+			});
+		}
+		self.pos = self.content.len();
+		None
+	}
+}
+
+// Contents of a `<...>` tag, without the angle brackets
+fn parse_tag(tag: &str) -> Option<TokenKind> {
+	if let Some(id) = tag.strip_prefix("@&") {
+		Some(TokenKind::Role(parse_id(id)?.into()))
+	} else if let Some(id) = tag.strip_prefix('@') {
+		let id = id.strip_prefix('!').unwrap_or(id);
+		Some(TokenKind::User(parse_id(id)?.into()))
+	} else if let Some(id) = tag.strip_prefix('#') {
+		Some(TokenKind::Channel(parse_id(id)?.into()))
+	} else if let Some(command) = tag.strip_prefix('/') {
+		let (name, id) = command.rsplit_once(':')?;
+		if name.is_empty() || name.starts_with(' ') || name.ends_with(' ') {
+			return None;
+		}
+		Some(TokenKind::Command {
+			name: name.to_owned(),
+			id: parse_id(id)?,
+		})
+	} else if let Some(timestamp) = tag.strip_prefix("t:") {
+		let (secs, style) = match timestamp.split_once(':') {
+			Some((secs, style)) => {
+				let mut chars = style.chars();
+				let style = match (chars.next(), chars.next()) {
+					(Some(c), None) => TimestampStyle::from_char(c)?,
+					_ => return None,
+				};
+				(secs, Some(style))
+			}
+			None => (timestamp, None),
+		};
+		let time = Utc.timestamp_opt(secs.parse().ok()?, 0).single()?;
+		Some(TokenKind::Timestamp {
+			time: time.into(),
+			style,
+		})
+	} else {
+		let (animated, emoji) = match tag.strip_prefix("a:") {
+			Some(emoji) => (true, emoji),
+			None => (false, tag.strip_prefix(':')?),
+		};
+		let (name, id) = emoji.split_once(':')?;
+		if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+			return None;
+		}
+		Some(TokenKind::Emoji(PartialEmoji {
+			id: Some(parse_id(id)?),
+			name: Some(name.to_owned().into()),
+			animated,
+		}))
+	}
+}
+
+fn parse_id(id: &str) -> Option<Snowflake> {
+	if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+		return None;
+	}
+	id.parse::<u64>().ok().map(Snowflake::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn kinds(content: &str) -> Vec<TokenKind> {
+		tokens(content).map(|t| t.kind).collect()
+	}
+
+	#[test]
+	fn parse() {
+		let content = "hi <@1> and <@!2>, see <#3> <@&4> <a:blob:5> <:ok:6> </ping:7> </role add:8> <t:1672531200:R> <t:0> @here";
+		let found: Vec<_> = tokens(content).collect();
+		assert_eq!(found.len(), 11);
+		for token in &found {
+			let text = &content[token.span.clone()];
+			let rendered = token.kind.to_string();
+			// <@!2> is rendered without the legacy nickname marker
+			if text != "<@!2>" {
+				assert_eq!(text, rendered);
+			}
+		}
+		assert_eq!(found[0].kind, TokenKind::User(1.into()));
+		assert_eq!(found[0].span, 3..7);
+		assert_eq!(found[1].kind, TokenKind::User(2.into()));
+		assert_eq!(found[2].kind, TokenKind::Channel(3.into()));
+		assert_eq!(found[3].kind, TokenKind::Role(4.into()));
+		match &found[4].kind {
+			TokenKind::Emoji(e) => {
+				assert!(e.animated);
+				assert_eq!(e.name.as_deref(), Some("blob"));
+				assert_eq!(e.id, Some(5.into()));
+			}
+			k => panic!("unexpected token {:?}", k),
+		}
+		assert_eq!(
+			found[7].kind,
+			TokenKind::Command {
+				name: "role add".into(),
+				id: 8.into()
+			}
+		);
+		match &found[8].kind {
+			TokenKind::Timestamp { time, style } => {
+				assert_eq!(time.timestamp(), 1672531200);
+				assert_eq!(*style, Some(TimestampStyle::Relative));
+			}
+			k => panic!("unexpected token {:?}", k),
+		}
+		assert_eq!(found[10].kind, TokenKind::Here);
+	}
+
+	#[test]
+	fn malformed() {
+		assert!(
+			kinds("<@> <@x> <@1 <#> <:name:> <::1> </:1> <t:1:x> <t:> @every1 <@+1> <:a b:1>")
+				.is_empty()
+		);
+		assert_eq!(kinds("<@x<@1>"), vec![TokenKind::User(1.into())]);
+		assert_eq!(kinds("é<@1>é"), vec![TokenKind::User(1.into())]);
+	}
+}
@@ -1,7 +1,13 @@
 use crate::{
-	AllowedMentions, ApplicationCommandOption, Component, CowString, Embed, InteractionResponseType,
+	AllowedMentions, ApplicationCommandOption, ApplicationId, AutoModerationAction,
+	AutoModerationEventType, ChannelId, Component, CowString, DateTime, Embed, EntityMetadata,
+	GuildScheduledEventEntityType, GuildScheduledEventStatus, InteractionResponseType,
+	InviteTargetType, Message, MessageFlags, MessageReference, PrivacyLevel, RecurrenceRule,
+	RoleId, Snowflake, TriggerMetadata, TriggerType, UserId,
 };
 use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Clone, Debug, Serialize)]
 pub struct CreateCommand<'a> {
@@ -10,36 +16,128 @@ pub struct CreateCommand<'a> {
 	pub options: Vec<ApplicationCommandOption>,
 }
 
+// A file to upload, sent as the `files[n]` part of a multipart body
 #[derive(Clone, Debug, Serialize)]
 pub struct Attachment {
 	pub name: CowString,
 	pub data: Vec<u8>,
 }
 
+// Entry of the `attachments` array of a message body: either an existing
+// attachment to keep, or metadata for the upload at the given index
 #[derive(Clone, Debug, Serialize)]
+pub struct PartialAttachment {
+	pub id: Snowflake,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub filename: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub description: Option<CowString>,
+}
+
+impl PartialAttachment {
+	pub fn upload(index: usize, attachment: &Attachment) -> Self {
+		Self {
+			id: (index as u64).into(),
+			filename: Some(attachment.name.clone()),
+			description: None,
+		}
+	}
+
+	pub fn description<T: Into<CowString>>(mut self, description: T) -> Self {
+		self.description = Some(description.into());
+		self
+	}
+}
+
+impl From<&crate::Attachment> for PartialAttachment {
+	fn from(attachment: &crate::Attachment) -> Self {
+		Self {
+			id: attachment.id,
+			filename: None,
+			description: None,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct CreateMessage {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub content: Option<CowString>,
 	// tts
 	#[serde(skip_serializing_if = "Vec::is_empty")]
 	pub embeds: Vec<Embed>,
-	// allowed_mentions
-	// message_reference
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub allowed_mentions: Option<AllowedMentions>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub message_reference: Option<MessageReference>,
 	#[serde(skip_serializing_if = "Vec::is_empty")]
 	pub components: Vec<Component>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub attachments: Vec<PartialAttachment>,
+	// Uploaded as multipart parts, see `multipart`
+	#[serde(skip)]
+	pub files: Vec<Attachment>,
 }
 
-#[derive(Clone, Debug, Serialize)]
+impl CreateMessage {
+	pub fn reply_to(message: &Message) -> Self {
+		Self {
+			message_reference: Some(message.into()),
+			..Default::default()
+		}
+	}
+
+	pub fn content<T: Into<CowString>>(mut self, content: T) -> Self {
+		self.content = Some(content.into());
+		self
+	}
+
+	pub fn multipart(&self) -> serde_json::Result<Multipart> {
+		Multipart::with_payload(self, &self.files)
+	}
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct EditMessage {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub content: Option<CowString>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub embeds: Option<Vec<Embed>>,
 	// flags
-	// allowed_mentions
-	// attachments
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub allowed_mentions: Option<AllowedMentions>,
+	// Existing attachments not listed here are removed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub attachments: Option<Vec<PartialAttachment>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub components: Option<Vec<Component>>,
+	#[serde(skip)]
+	pub files: Vec<Attachment>,
+}
+
+impl EditMessage {
+	pub fn multipart(&self) -> serde_json::Result<Multipart> {
+		Multipart::with_payload(self, &self.files)
+	}
+}
+
+// Starts a thread in a forum or media channel, together with its first message
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateForumPost {
+	pub name: CowString,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub auto_archive_duration: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub rate_limit_per_user: Option<u16>,
+	pub message: CreateMessage,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub applied_tags: Vec<Snowflake>,
+}
+
+impl CreateForumPost {
+	pub fn multipart(&self) -> serde_json::Result<Multipart> {
+		Multipart::with_payload(self, &self.message.files)
+	}
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -50,6 +148,207 @@ pub struct CreateGuildBan<'a> {
 	pub reason: Option<&'a str>,
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateGuildScheduledEvent {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub channel_id: Option<ChannelId>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub entity_metadata: Option<EntityMetadata>,
+	pub name: CowString,
+	pub privacy_level: PrivacyLevel,
+	pub scheduled_start_time: DateTime,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub scheduled_end_time: Option<DateTime>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub description: Option<CowString>,
+	pub entity_type: GuildScheduledEventEntityType,
+	// Data URI of the cover image
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub image: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub recurrence_rule: Option<RecurrenceRule>,
+}
+
+// Fields set to `Some(None)` are cleared
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ModifyGuildScheduledEvent {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub channel_id: Option<Option<ChannelId>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub entity_metadata: Option<Option<EntityMetadata>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub name: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub privacy_level: Option<PrivacyLevel>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub scheduled_start_time: Option<DateTime>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub scheduled_end_time: Option<DateTime>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub description: Option<Option<CowString>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub entity_type: Option<GuildScheduledEventEntityType>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub status: Option<GuildScheduledEventStatus>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub image: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub recurrence_rule: Option<Option<RecurrenceRule>>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateAutoModerationRule {
+	pub name: CowString,
+	pub event_type: AutoModerationEventType,
+	pub trigger_type: TriggerType,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub trigger_metadata: Option<TriggerMetadata>,
+	pub actions: Vec<AutoModerationAction>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub enabled: Option<bool>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub exempt_roles: Vec<RoleId>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub exempt_channels: Vec<ChannelId>,
+}
+
+// The trigger type of an existing rule can't be changed
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ModifyAutoModerationRule {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub name: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub event_type: Option<AutoModerationEventType>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub trigger_metadata: Option<TriggerMetadata>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub actions: Option<Vec<AutoModerationAction>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub enabled: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub exempt_roles: Option<Vec<RoleId>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub exempt_channels: Option<Vec<ChannelId>>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateChannelInvite {
+	// Seconds, 0 for never; defaults to 24 hours
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_age: Option<u32>,
+	// 0 for unlimited
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_uses: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub temporary: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub unique: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub target_type: Option<InviteTargetType>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub target_user_id: Option<UserId>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub target_application_id: Option<ApplicationId>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ExecuteWebhook {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub content: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub username: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub avatar_url: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub tts: Option<bool>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub embeds: Vec<Embed>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub allowed_mentions: Option<AllowedMentions>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub components: Vec<Component>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub flags: Option<MessageFlags>,
+	// Creates a forum post
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub thread_name: Option<CowString>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub applied_tags: Vec<Snowflake>,
+	// Sent as query parameters, see `query`
+	#[serde(skip)]
+	pub thread_id: Option<ChannelId>,
+	#[serde(skip)]
+	pub wait: bool,
+}
+
+impl ExecuteWebhook {
+	// Query string for the execute webhook url, empty if there are no parameters
+	pub fn query(&self) -> String {
+		let mut params = Vec::new();
+		if self.wait {
+			params.push("wait=true".to_owned());
+		}
+		if let Some(thread_id) = self.thread_id {
+			params.push(format!("thread_id={}", thread_id));
+		}
+		if params.is_empty() {
+			String::new()
+		} else {
+			format!("?{}", params.join("&"))
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateStageInstance {
+	pub channel_id: ChannelId,
+	pub topic: CowString,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub privacy_level: Option<PrivacyLevel>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub send_start_notification: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub guild_scheduled_event_id: Option<Snowflake>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ModifyStageInstance {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub topic: Option<CowString>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub privacy_level: Option<PrivacyLevel>,
+}
+
+// Modify the voice state of the current user in a stage channel
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ModifyCurrentUserVoiceState {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub channel_id: Option<ChannelId>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub suppress: Option<bool>,
+	// `Some(None)` withdraws the request to speak
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub request_to_speak_timestamp: Option<Option<DateTime>>,
+}
+
+impl ModifyCurrentUserVoiceState {
+	pub fn request_to_speak(channel_id: ChannelId, timestamp: DateTime) -> Self {
+		Self {
+			channel_id: Some(channel_id),
+			suppress: None,
+			request_to_speak_timestamp: Some(Some(timestamp)),
+		}
+	}
+}
+
+// Modify the voice state of another user in a stage channel, e.g. to invite them to speak
+#[derive(Clone, Debug, Serialize)]
+pub struct ModifyUserVoiceState {
+	pub channel_id: ChannelId,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub suppress: Option<bool>,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct InteractionResponse<'a> {
 	#[serde(rename = "type")]
@@ -58,7 +357,14 @@ pub struct InteractionResponse<'a> {
 	pub data: Option<InteractionCallbackData<'a>>,
 }
 
-#[derive(Clone, Debug, Serialize)]
+impl<'a> InteractionResponse<'a> {
+	pub fn multipart(&self) -> serde_json::Result<Multipart> {
+		let files = self.data.as_ref().map_or(&[][..], |d| &d.files[..]);
+		Multipart::with_payload(self, files)
+	}
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct InteractionCallbackData<'a> {
 	pub tts: bool,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -71,17 +377,380 @@ pub struct InteractionCallbackData<'a> {
 	pub flags: Option<u32>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub components: Option<Vec<Component>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub attachments: Option<Vec<PartialAttachment>>,
+	#[serde(skip)]
+	pub files: Vec<Attachment>,
+}
+
+// A `multipart/form-data` body; send `content_type()` as the Content-Type
+// header and `finish()` as the request body
+#[derive(Clone, Debug)]
+pub struct Multipart {
+	boundary: String,
+	body: Vec<u8>,
 }
 
-impl Default for InteractionCallbackData<'_> {
+impl Default for Multipart {
 	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Multipart {
+	pub fn new() -> Self {
+		static COUNTER: AtomicU64 = AtomicU64::new(0);
+		let nanos = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_nanos() as u64)
+			.unwrap_or(0);
+		let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+		Self::with_boundary(format!("{:016x}{:08x}", nanos, count))
+	}
+
+	// The boundary is replaced with a generated one if a part contains it
+	pub fn with_boundary<T: Into<String>>(boundary: T) -> Self {
 		Self {
-			tts: false,
-			content: None,
-			embeds: None,
-			allowed_mentions: None,
-			flags: None,
-			components: None,
+			boundary: boundary.into(),
+			body: Vec::new(),
 		}
 	}
+
+	// Body with `payload` as `payload_json` and the files as `files[n]`
+	pub fn with_payload<T: Serialize>(
+		payload: &T,
+		files: &[Attachment],
+	) -> serde_json::Result<Self> {
+		let mut multipart = Self::new();
+		multipart.json("payload_json", payload)?;
+		for (i, file) in files.iter().enumerate() {
+			multipart.file(&format!("files[{}]", i), file);
+		}
+		Ok(multipart)
+	}
+
+	pub fn boundary(&self) -> &str {
+		&self.boundary
+	}
+
+	pub fn content_type(&self) -> String {
+		format!("multipart/form-data; boundary={}", self.boundary)
+	}
+
+	pub fn json<T: Serialize>(&mut self, name: &str, value: &T) -> serde_json::Result<&mut Self> {
+		let data = serde_json::to_vec(value)?;
+		Ok(self.part(name, None, "application/json", &data))
+	}
+
+	pub fn file(&mut self, name: &str, file: &Attachment) -> &mut Self {
+		self.part(
+			name,
+			Some(&file.name),
+			"application/octet-stream",
+			&file.data,
+		)
+	}
+
+	pub fn part(
+		&mut self,
+		name: &str,
+		filename: Option<&str>,
+		content_type: &str,
+		data: &[u8],
+	) -> &mut Self {
+		let mut part = Vec::with_capacity(data.len() + 128);
+		part.extend_from_slice(b"\r\nContent-Disposition: form-data; name=\"");
+		part.extend_from_slice(escape(name).as_bytes());
+		part.push(b'"');
+		if let Some(filename) = filename {
+			part.extend_from_slice(b"; filename=\"");
+			part.extend_from_slice(escape(filename).as_bytes());
+			part.push(b'"');
+		}
+		part.extend_from_slice(b"\r\nContent-Type: ");
+		// A line break would end the header, so drop them instead of escaping
+		part.extend_from_slice(content_type.replace(['\r', '\n'], "").as_bytes());
+		part.extend_from_slice(b"\r\n\r\n");
+		part.extend_from_slice(data);
+		part.extend_from_slice(b"\r\n");
+
+		if contains(&part, self.boundary.as_bytes()) {
+			self.change_boundary(&part);
+		}
+		self.body.extend_from_slice(b"--");
+		self.body.extend_from_slice(self.boundary.as_bytes());
+		self.body.extend_from_slice(&part);
+		self
+	}
+
+	// Pick a boundary that occurs in neither the body nor `part`, and rewrite the
+	// delimiters already in the body; the old boundary only occurs in those
+	fn change_boundary(&mut self, part: &[u8]) {
+		let boundary = loop {
+			let boundary = Self::new().boundary;
+			if !contains(&self.body, boundary.as_bytes()) && !contains(part, boundary.as_bytes()) {
+				break boundary;
+			}
+		};
+		let old = self.boundary.as_bytes();
+		let mut body = Vec::with_capacity(self.body.len());
+		let mut rest = &self.body[..];
+		while !rest.is_empty() {
+			// An empty boundary collides with the first part, before anything is written
+			if !old.is_empty() && rest.starts_with(old) {
+				body.extend_from_slice(boundary.as_bytes());
+				rest = &rest[old.len()..];
+			} else {
+				body.push(rest[0]);
+				rest = &rest[1..];
+			}
+		}
+		self.body = body;
+		self.boundary = boundary;
+	}
+
+	// Appends the closing boundary and returns the complete body
+	pub fn finish(mut self) -> Vec<u8> {
+		self.body.extend_from_slice(b"--");
+		self.body.extend_from_slice(self.boundary.as_bytes());
+		self.body.extend_from_slice(b"--\r\n");
+		self.body
+	}
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+	needle.is_empty() || haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+// Percent-encode the characters that would end a quoted header parameter
+fn escape(value: &str) -> String {
+	value
+		.replace('"', "%22")
+		.replace('\r', "%0D")
+		.replace('\n', "%0A")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn multipart() {
+		let file = Attachment {
+			name: "a\"b.txt".into(),
+			data: b"hello".to_vec(),
+		};
+		let message = CreateMessage {
+			content: Some("hi".into()),
+			attachments: vec![PartialAttachment::upload(0, &file).description("greeting")],
+			files: vec![file],
+			..Default::default()
+		};
+		let multipart = message.multipart().unwrap();
+		let boundary = multipart.boundary().to_owned();
+		assert_eq!(
+			multipart.content_type(),
+			format!("multipart/form-data; boundary={}", boundary)
+		);
+		let body = String::from_utf8(multipart.finish()).unwrap();
+		let expected = format!(
+			"--{b}\r\n\
+			Content-Disposition: form-data; name=\"payload_json\"\r\n\
+			Content-Type: application/json\r\n\r\n\
+			{{\"content\":\"hi\",\"attachments\":[{{\"id\":\"0\",\"filename\":\"a\\\"b.txt\",\"description\":\"greeting\"}}]}}\r\n\
+			--{b}\r\n\
+			Content-Disposition: form-data; name=\"files[0]\"; filename=\"a%22b.txt\"\r\n\
+			Content-Type: application/octet-stream\r\n\r\n\
+			hello\r\n\
+			--{b}--\r\n",
+			b = boundary
+		);
+		assert_eq!(body, expected);
+	}
+
+	#[test]
+	fn multipart_boundary_collision() {
+		let mut multipart = Multipart::with_boundary("boundary");
+		multipart.part("a", None, "text/plain", b"first");
+		multipart.part("b", None, "text/plain", b"--boundary--");
+		let boundary = multipart.boundary().to_owned();
+		assert_ne!(boundary, "boundary");
+		let body = String::from_utf8(multipart.finish()).unwrap();
+		let expected = format!(
+			"--{b}\r\n\
+			Content-Disposition: form-data; name=\"a\"\r\n\
+			Content-Type: text/plain\r\n\r\n\
+			first\r\n\
+			--{b}\r\n\
+			Content-Disposition: form-data; name=\"b\"\r\n\
+			Content-Type: text/plain\r\n\r\n\
+			--boundary--\r\n\
+			--{b}--\r\n",
+			b = boundary
+		);
+		assert_eq!(body, expected);
+
+		let mut multipart = Multipart::with_boundary("");
+		multipart.part("a", None, "text/plain", b"first");
+		assert!(!multipart.boundary().is_empty());
+	}
+
+	#[test]
+	fn multipart_header_injection() {
+		let mut multipart = Multipart::with_boundary("b");
+		multipart.part(
+			"a\r\nX-Injected: 1",
+			Some("c\r\n.txt"),
+			"text/plain\r\nX-Injected: 1\r\n\r\n",
+			b"data",
+		);
+		let body = String::from_utf8(multipart.finish()).unwrap();
+		assert_eq!(
+			body,
+			"--b\r\n\
+			Content-Disposition: form-data; name=\"a%0D%0AX-Injected: 1\"; filename=\"c%0D%0A.txt\"\r\n\
+			Content-Type: text/plainX-Injected: 1\r\n\r\n\
+			data\r\n\
+			--b--\r\n"
+		);
+	}
+
+	#[test]
+	fn edit_message_multipart() {
+		let file = Attachment {
+			name: "b.png".into(),
+			data: b"png".to_vec(),
+		};
+		let edit = EditMessage {
+			content: Some("edited".into()),
+			attachments: Some(vec![PartialAttachment::upload(0, &file)]),
+			files: vec![file],
+			..Default::default()
+		};
+		let multipart = edit.multipart().unwrap();
+		let boundary = multipart.boundary().to_owned();
+		let body = String::from_utf8(multipart.finish()).unwrap();
+		let expected = format!(
+			"--{b}\r\n\
+			Content-Disposition: form-data; name=\"payload_json\"\r\n\
+			Content-Type: application/json\r\n\r\n\
+			{{\"content\":\"edited\",\"attachments\":[{{\"id\":\"0\",\"filename\":\"b.png\"}}]}}\r\n\
+			--{b}\r\n\
+			Content-Disposition: form-data; name=\"files[0]\"; filename=\"b.png\"\r\n\
+			Content-Type: application/octet-stream\r\n\r\n\
+			png\r\n\
+			--{b}--\r\n",
+			b = boundary
+		);
+		assert_eq!(body, expected);
+	}
+
+	#[test]
+	fn interaction_response_multipart() {
+		let file = Attachment {
+			name: "c.txt".into(),
+			data: b"text".to_vec(),
+		};
+		let response = InteractionResponse {
+			response_type: InteractionResponseType::ChannelMessage,
+			data: Some(InteractionCallbackData {
+				content: Some("done"),
+				attachments: Some(vec![PartialAttachment::upload(0, &file)]),
+				files: vec![file],
+				..Default::default()
+			}),
+		};
+		let multipart = response.multipart().unwrap();
+		let boundary = multipart.boundary().to_owned();
+		let body = String::from_utf8(multipart.finish()).unwrap();
+		let expected = format!(
+			"--{b}\r\n\
+			Content-Disposition: form-data; name=\"payload_json\"\r\n\
+			Content-Type: application/json\r\n\r\n\
+			{{\"type\":4,\"data\":{{\"tts\":false,\"content\":\"done\",\"attachments\":[{{\"id\":\"0\",\"filename\":\"c.txt\"}}]}}}}\r\n\
+			--{b}\r\n\
+			Content-Disposition: form-data; name=\"files[0]\"; filename=\"c.txt\"\r\n\
+			Content-Type: application/octet-stream\r\n\r\n\
+			text\r\n\
+			--{b}--\r\n",
+			b = boundary
+		);
+		assert_eq!(body, expected);
+
+		// Responses without data have no files
+		let pong = InteractionResponse {
+			response_type: InteractionResponseType::Pong,
+			data: None,
+		};
+		let multipart = pong.multipart().unwrap();
+		let boundary = multipart.boundary().to_owned();
+		let body = String::from_utf8(multipart.finish()).unwrap();
+		assert_eq!(
+			body,
+			format!(
+				"--{b}\r\n\
+				Content-Disposition: form-data; name=\"payload_json\"\r\n\
+				Content-Type: application/json\r\n\r\n\
+				{{\"type\":1}}\r\n\
+				--{b}--\r\n",
+				b = boundary
+			)
+		);
+	}
+
+	#[test]
+	fn reply_to() {
+		let message: Message = serde_json::from_str(
+			r#"{"id":"3","channel_id":"4","guild_id":"1","content":"!ping","edited_timestamp":null,"type":0}"#,
+		)
+		.unwrap();
+		let reply = CreateMessage::reply_to(&message).content("pong");
+		assert_eq!(
+			serde_json::to_string(&reply).unwrap(),
+			r#"{"content":"pong","message_reference":{"type":0,"message_id":"3","channel_id":"4","guild_id":"1"}}"#
+		);
+	}
+
+	#[test]
+	fn execute_webhook_query() {
+		let mut webhook = ExecuteWebhook {
+			content: Some("hi".into()),
+			..Default::default()
+		};
+		assert_eq!(webhook.query(), "");
+		webhook.wait = true;
+		assert_eq!(webhook.query(), "?wait=true");
+		webhook.thread_id = Some(5.into());
+		assert_eq!(webhook.query(), "?wait=true&thread_id=5");
+		webhook.wait = false;
+		assert_eq!(webhook.query(), "?thread_id=5");
+		// Query parameters are not part of the body
+		assert_eq!(
+			serde_json::to_string(&webhook).unwrap(),
+			r#"{"content":"hi"}"#
+		);
+	}
+
+	#[test]
+	fn create_forum_post() {
+		let mut post = CreateForumPost {
+			name: "help".into(),
+			auto_archive_duration: None,
+			rate_limit_per_user: None,
+			message: CreateMessage::default().content("first"),
+			applied_tags: vec![12.into(), 13.into()],
+		};
+		assert_eq!(
+			serde_json::to_string(&post).unwrap(),
+			r#"{"name":"help","message":{"content":"first"},"applied_tags":["12","13"]}"#
+		);
+
+		post.auto_archive_duration = Some(1440);
+		post.rate_limit_per_user = Some(60);
+		post.applied_tags.clear();
+		assert_eq!(
+			serde_json::to_string(&post).unwrap(),
+			r#"{"name":"help","auto_archive_duration":1440,"rate_limit_per_user":60,"message":{"content":"first"}}"#
+		);
+	}
 }
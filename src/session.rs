@@ -0,0 +1,362 @@
+use crate::command::{Heartbeat, Identify, Resume};
+use crate::event::{CloseCode, Event, Payload, Recovery};
+use crate::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Debug)]
+pub enum Action {
+	Send(Command),
+	// Close the connection and open a new one, resuming the session if possible
+	Reconnect { resume: bool },
+}
+
+// Transport agnostic gateway session: feed it received payloads and clock ticks,
+// and it returns the commands to send and when to reconnect
+#[derive(Clone, Debug)]
+pub struct GatewaySession {
+	identify: Identify,
+	session_id: Option<String>,
+	resume_gateway_url: Option<String>,
+	sequence: Option<u64>,
+	heartbeat_interval: Option<Duration>,
+	next_heartbeat: Option<Instant>,
+	awaiting_ack: bool,
+	identify_at: Option<Instant>,
+	rng: u64,
+}
+
+impl GatewaySession {
+	pub fn new(identify: Identify) -> Self {
+		let seed = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_nanos() as u64)
+			.unwrap_or(0);
+		Self::with_seed(identify, seed)
+	}
+
+	// Seed for the heartbeat and identify jitter, so tests are deterministic
+	pub fn with_seed(identify: Identify, seed: u64) -> Self {
+		Self {
+			identify,
+			session_id: None,
+			resume_gateway_url: None,
+			sequence: None,
+			heartbeat_interval: None,
+			next_heartbeat: None,
+			awaiting_ack: false,
+			identify_at: None,
+			rng: seed | 1,
+		}
+	}
+
+	pub fn session_id(&self) -> Option<&str> {
+		self.session_id.as_deref()
+	}
+
+	pub fn resume_gateway_url(&self) -> Option<&str> {
+		self.resume_gateway_url.as_deref()
+	}
+
+	pub fn sequence(&self) -> Option<u64> {
+		self.sequence
+	}
+
+	pub fn can_resume(&self) -> bool {
+		self.session_id.is_some() && self.sequence.is_some()
+	}
+
+	// Next point in time at which `tick` has to be called
+	pub fn deadline(&self) -> Option<Instant> {
+		match (self.next_heartbeat, self.identify_at) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		}
+	}
+
+	// The connection was closed; the session itself is kept so it can be resumed
+	pub fn disconnected(&mut self) {
+		self.heartbeat_interval = None;
+		self.next_heartbeat = None;
+		self.awaiting_ack = false;
+		self.identify_at = None;
+	}
+
+	// The gateway closed the connection, `None` if it did so without a close code
+	pub fn closed(&mut self, code: Option<CloseCode>) -> Recovery {
+		self.disconnected();
+		let recovery = match code {
+			Some(code) => code.recovery(),
+			None => Recovery::Resume,
+		};
+		match recovery {
+			Recovery::Resume if self.can_resume() => Recovery::Resume,
+			Recovery::Resume | Recovery::Identify => {
+				self.invalidate();
+				Recovery::Identify
+			}
+			Recovery::Fatal => Recovery::Fatal,
+		}
+	}
+
+	// Forget the session, the next connection will identify
+	pub fn invalidate(&mut self) {
+		self.session_id = None;
+		self.resume_gateway_url = None;
+		self.sequence = None;
+	}
+
+	pub fn receive(&mut self, payload: &Payload, now: Instant) -> Option<Action> {
+		if let Some(seq) = payload.sequence {
+			self.sequence = Some(seq);
+		}
+
+		match &payload.event {
+			Event::Hello(hello) => {
+				let interval = Duration::from_millis(hello.heartbeat_interval);
+				self.heartbeat_interval = Some(interval);
+				self.next_heartbeat = Some(now + interval.mul_f64(self.jitter()));
+				self.awaiting_ack = false;
+				self.identify_at = None;
+				Some(Action::Send(self.handshake()))
+			}
+			Event::Ready(ready) => {
+				self.session_id = Some(ready.session_id.clone());
+				self.resume_gateway_url = ready.resume_gateway_url.clone();
+				None
+			}
+			Event::HeartbeatAck => {
+				self.awaiting_ack = false;
+				None
+			}
+			Event::HeartbeatRequest => Some(Action::Send(self.heartbeat())),
+			Event::Reconnect => {
+				self.disconnected();
+				Some(Action::Reconnect {
+					resume: self.can_resume(),
+				})
+			}
+			Event::InvalidSession(invalid) if invalid.resumable && self.can_resume() => {
+				self.disconnected();
+				Some(Action::Reconnect { resume: true })
+			}
+			Event::InvalidSession(_) => {
+				// Discord asks to wait a random 1-5 seconds before identifying again
+				self.invalidate();
+				let delay = Duration::from_secs(1) + Duration::from_secs(4).mul_f64(self.jitter());
+				self.identify_at = Some(now + delay);
+				None
+			}
+			_ => None,
+		}
+	}
+
+	pub fn tick(&mut self, now: Instant) -> Option<Action> {
+		if let Some(at) = self.identify_at {
+			if at <= now {
+				self.identify_at = None;
+				return Some(Action::Send(self.identify.clone().into()));
+			}
+		}
+
+		match (self.next_heartbeat, self.heartbeat_interval) {
+			(Some(at), Some(interval)) if at <= now => {
+				if self.awaiting_ack {
+					// No ack since the last heartbeat: zombied connection
+					self.disconnected();
+					return Some(Action::Reconnect {
+						resume: self.can_resume(),
+					});
+				}
+				self.next_heartbeat = Some(now + interval);
+				Some(Action::Send(self.heartbeat()))
+			}
+			_ => None,
+		}
+	}
+
+	fn handshake(&self) -> Command {
+		match (&self.session_id, self.sequence) {
+			(Some(session_id), Some(seq)) => Resume {
+				token: self.identify.token.clone(),
+				session_id: session_id.clone().into(),
+				seq,
+			}
+			.into(),
+			_ => self.identify.clone().into(),
+		}
+	}
+
+	fn heartbeat(&mut self) -> Command {
+		self.awaiting_ack = true;
+		Heartbeat::from(self.sequence).into()
+	}
+
+	// xorshift64, uniform in [0, 1)
+	fn jitter(&mut self) -> f64 {
+		self.rng ^= self.rng << 13;
+		self.rng ^= self.rng >> 7;
+		self.rng ^= self.rng << 17;
+		(self.rng >> 11) as f64 / (1u64 << 53) as f64
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::command::ConnectionProperties;
+
+	fn session() -> GatewaySession {
+		GatewaySession::with_seed(
+			Identify {
+				token: "token".into(),
+				properties: ConnectionProperties {
+					os: "linux".into(),
+					browser: "test".into(),
+					device: "test".into(),
+				},
+				compress: None,
+				large_threshold: None,
+				shard: None,
+				presence: None,
+				guild_subscriptions: None,
+				intents: None,
+			},
+			42,
+		)
+	}
+
+	fn payload(json: &str) -> Payload {
+		serde_json::from_str(json).unwrap()
+	}
+
+	fn ready() -> Payload {
+		payload(
+			r#"{"t":"READY","s":1,"op":0,"d":{"v":10,"user":{"id":"1","username":"bot","avatar":null},"guilds":[],"session_id":"abc","resume_gateway_url":"wss://resume","application":{"id":"2","flags":0}}}"#,
+		)
+	}
+
+	fn hello() -> Payload {
+		payload(r#"{"op":10,"d":{"heartbeat_interval":1000}}"#)
+	}
+
+	fn ack() -> Payload {
+		payload(r#"{"op":11,"d":null}"#)
+	}
+
+	#[test]
+	fn identify_and_heartbeat() {
+		let mut session = session();
+		let now = Instant::now();
+
+		match session.receive(&hello(), now) {
+			Some(Action::Send(Command::Identify(_))) => {}
+			a => panic!("unexpected action {:?}", a),
+		}
+		let first = session.deadline().unwrap();
+		assert!(first > now && first < now + Duration::from_millis(1000));
+		assert!(session.tick(now).is_none());
+
+		assert!(session.receive(&ready(), now).is_none());
+		assert_eq!(session.session_id(), Some("abc"));
+		assert_eq!(session.resume_gateway_url(), Some("wss://resume"));
+
+		match session.tick(first) {
+			Some(Action::Send(Command::Heartbeat(h))) => assert_eq!(h.sequence, Some(1)),
+			a => panic!("unexpected action {:?}", a),
+		}
+		assert_eq!(
+			session.deadline(),
+			Some(first + Duration::from_millis(1000))
+		);
+		session.receive(&ack(), first);
+
+		match session.tick(first + Duration::from_millis(1000)) {
+			Some(Action::Send(Command::Heartbeat(_))) => {}
+			a => panic!("unexpected action {:?}", a),
+		}
+	}
+
+	#[test]
+	fn zombie_connection_resumes() {
+		let mut session = session();
+		let now = Instant::now();
+		session.receive(&hello(), now);
+		session.receive(&ready(), now);
+
+		let first = session.deadline().unwrap();
+		assert!(matches!(session.tick(first), Some(Action::Send(_))));
+		let second = session.deadline().unwrap();
+		match session.tick(second) {
+			Some(Action::Reconnect { resume: true }) => {}
+			a => panic!("unexpected action {:?}", a),
+		}
+		assert_eq!(session.deadline(), None);
+
+		match session.receive(&hello(), second) {
+			Some(Action::Send(Command::Resume(r))) => {
+				assert_eq!(r.session_id, "abc");
+				assert_eq!(r.seq, 1);
+			}
+			a => panic!("unexpected action {:?}", a),
+		}
+	}
+
+	#[test]
+	fn invalid_session() {
+		let mut session = session();
+		let now = Instant::now();
+		session.receive(&hello(), now);
+		session.receive(&ready(), now);
+
+		match session.receive(&payload(r#"{"op":9,"d":true}"#), now) {
+			Some(Action::Reconnect { resume: true }) => {}
+			a => panic!("unexpected action {:?}", a),
+		}
+
+		session.receive(&hello(), now);
+		assert!(session
+			.receive(&payload(r#"{"op":9,"d":false}"#), now)
+			.is_none());
+		assert!(!session.can_resume());
+		let at = session.identify_at.unwrap();
+		assert!(at >= now + Duration::from_secs(1));
+		assert!(at <= now + Duration::from_secs(5));
+		match session.tick(at) {
+			Some(Action::Send(Command::Identify(_))) => {}
+			a => panic!("unexpected action {:?}", a),
+		}
+	}
+
+	#[test]
+	fn close_codes() {
+		let mut session = session();
+		let now = Instant::now();
+		session.receive(&hello(), now);
+		assert_eq!(session.closed(Some(4000.into())), Recovery::Identify);
+
+		session.receive(&hello(), now);
+		session.receive(&ready(), now);
+		assert_eq!(session.closed(None), Recovery::Resume);
+		assert_eq!(session.deadline(), None);
+		assert_eq!(
+			session.closed(Some(CloseCode::DisallowedIntents)),
+			Recovery::Fatal
+		);
+		assert_eq!(
+			session.closed(Some(CloseCode::SessionTimedOut)),
+			Recovery::Identify
+		);
+		assert!(!session.can_resume());
+	}
+
+	#[test]
+	fn heartbeat_request() {
+		let mut session = session();
+		let now = Instant::now();
+		session.receive(&hello(), now);
+		match session.receive(&payload(r#"{"op":1,"d":null}"#), now) {
+			Some(Action::Send(Command::Heartbeat(h))) => assert_eq!(h.sequence, None),
+			a => panic!("unexpected action {:?}", a),
+		}
+	}
+}
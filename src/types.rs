@@ -1,10 +1,10 @@
 use crate::bitflags::BitFlagsVisitor;
+use crate::markup::{self, TokenKind};
 use crate::CowString;
 use chrono::{Duration, TimeZone, Utc};
 use serde::de::{Unexpected, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use std::cmp::{Ordering, PartialEq, PartialOrd};
 use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::fmt;
@@ -94,7 +94,7 @@ impl<'de> Deserialize<'de> for Snowflake {
 				E: serde::de::Error,
 			{
 				val.parse()
-					.map(|v| Snowflake(v))
+					.map(Snowflake)
 					.map_err(|_| E::invalid_value(Unexpected::Str(val), &self))
 			}
 		}
@@ -153,7 +153,7 @@ where
 	}
 }
 
-#[derive(Clone, Debug, Eq, Ord)]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct DateTime(chrono::DateTime<Utc>);
 
 impl DateTime {
@@ -177,12 +177,6 @@ impl Deref for DateTime {
 	}
 }
 
-impl PartialEq for DateTime {
-	fn eq(&self, other: &Self) -> bool {
-		PartialEq::eq(&self.0, &other.0)
-	}
-}
-
 impl Sub<DateTime> for DateTime {
 	type Output = Duration;
 
@@ -191,12 +185,6 @@ impl Sub<DateTime> for DateTime {
 	}
 }
 
-impl PartialOrd for DateTime {
-	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-		PartialOrd::partial_cmp(&self.0, &other.0)
-	}
-}
-
 impl From<chrono::DateTime<Utc>> for DateTime {
 	fn from(datetime: chrono::DateTime<Utc>) -> Self {
 		Self(datetime)
@@ -222,7 +210,7 @@ impl<'de> Deserialize<'de> for DateTime {
 				E: serde::de::Error,
 			{
 				val.parse()
-					.map(|v| DateTime(v))
+					.map(DateTime)
 					.map_err(|_| E::invalid_value(Unexpected::Str(val), &self))
 			}
 		}
@@ -231,6 +219,15 @@ impl<'de> Deserialize<'de> for DateTime {
 	}
 }
 
+impl Serialize for DateTime {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&self.0.to_rfc3339())
+	}
+}
+
 #[cfg(feature = "sqlx")]
 impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for DateTime
 where
@@ -392,16 +389,17 @@ id_types!(
 	InteractionId,
 	MessageId,
 	RoleId,
-	UserId
+	UserId,
+	WebhookId
 );
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct Application {
 	pub id: ApplicationId,
 	pub flags: u64,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Channel {
 	pub id: ChannelId,
 	#[serde(rename = "type")]
@@ -410,7 +408,8 @@ pub struct Channel {
 	pub guild_id: Option<GuildId>,
 	#[serde(default)]
 	pub position: Option<u16>,
-	// permission_overwrites
+	#[serde(default)]
+	pub permission_overwrites: Vec<PermissionOverwrite>,
 	#[serde(default)]
 	pub name: Option<String>,
 	#[serde(default)]
@@ -427,13 +426,42 @@ pub struct Channel {
 	// recipients
 	// #[serde(default)]
 	// pub icon: Option<String>,
-	// #[serde(default)]
-	// pub owner_id: Option<Snowflake>,
+	#[serde(default)]
+	pub owner_id: Option<UserId>,
 	// #[serde(default)]
 	// pub application_id: Option<Snowflake>,
 	#[serde(default)]
 	pub parent_id: Option<ChannelId>,
-	// last_pin_timestamp
+	#[serde(default)]
+	pub last_pin_timestamp: Option<DateTime>,
+	#[serde(default)]
+	pub thread_metadata: Option<ThreadMetadata>,
+	#[serde(default)]
+	pub member_count: Option<u32>,
+	#[serde(default)]
+	pub message_count: Option<u32>,
+	#[serde(default)]
+	pub applied_tags: Vec<Snowflake>,
+	#[serde(default)]
+	pub member: Option<ThreadMember>,
+	#[serde(default)]
+	pub flags: Option<ChannelFlags>,
+	#[serde(default)]
+	pub available_tags: Vec<ForumTag>,
+	#[serde(default)]
+	pub default_reaction_emoji: Option<DefaultReaction>,
+	#[serde(default)]
+	pub default_thread_rate_limit_per_user: Option<u16>,
+	#[serde(default)]
+	pub default_sort_order: Option<SortOrderType>,
+	#[serde(default)]
+	pub default_forum_layout: Option<ForumLayoutType>,
+}
+
+impl Channel {
+	pub fn is_thread(&self) -> bool {
+		self.channel_type.is_thread()
+	}
 }
 
 impl fmt::Display for Channel {
@@ -445,7 +473,122 @@ impl fmt::Display for Channel {
 	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ForumTag {
+	pub id: Snowflake,
+	pub name: CowString,
+	#[serde(default)]
+	pub moderated: bool,
+	#[serde(default)]
+	pub emoji_id: Option<Snowflake>,
+	#[serde(default)]
+	pub emoji_name: Option<CowString>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DefaultReaction {
+	#[serde(default)]
+	pub emoji_id: Option<Snowflake>,
+	#[serde(default)]
+	pub emoji_name: Option<CowString>,
+}
+
+impl From<&DefaultReaction> for PartialEmoji {
+	fn from(reaction: &DefaultReaction) -> Self {
+		Self {
+			id: reaction.emoji_id,
+			name: reaction.emoji_name.clone(),
+			animated: false,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThreadMetadata {
+	pub archived: bool,
+	// Minutes of inactivity after which the thread is archived
+	pub auto_archive_duration: u32,
+	pub archive_timestamp: DateTime,
+	pub locked: bool,
+	#[serde(default)]
+	pub invitable: Option<bool>,
+	#[serde(default)]
+	pub create_timestamp: Option<DateTime>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ThreadMember {
+	// Omitted in the member list of GUILD_CREATE
+	#[serde(default)]
+	pub id: Option<ChannelId>,
+	#[serde(default)]
+	pub user_id: Option<UserId>,
+	pub join_timestamp: DateTime,
+	pub flags: u64,
+	#[serde(default)]
+	pub member: Option<Member>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(from = "RawPermissionOverwrite", into = "RawPermissionOverwrite")]
+pub struct PermissionOverwrite {
+	pub target: PermissionOverwriteTarget,
+	pub allow: Permissions,
+	pub deny: Permissions,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PermissionOverwriteTarget {
+	Role(RoleId),
+	Member(UserId),
+	// Kept so it can be sent back, but never applied
+	Unknown { id: Snowflake, overwrite_type: u8 },
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawPermissionOverwrite {
+	id: Snowflake,
+	#[serde(rename = "type")]
+	overwrite_type: u8,
+	allow: Permissions,
+	deny: Permissions,
+}
+
+impl From<RawPermissionOverwrite> for PermissionOverwrite {
+	fn from(raw: RawPermissionOverwrite) -> Self {
+		let target = match raw.overwrite_type {
+			0 => PermissionOverwriteTarget::Role(raw.id.into()),
+			1 => PermissionOverwriteTarget::Member(raw.id.into()),
+			overwrite_type => PermissionOverwriteTarget::Unknown {
+				id: raw.id,
+				overwrite_type,
+			},
+		};
+		Self {
+			target,
+			allow: raw.allow,
+			deny: raw.deny,
+		}
+	}
+}
+
+impl From<PermissionOverwrite> for RawPermissionOverwrite {
+	fn from(overwrite: PermissionOverwrite) -> Self {
+		let (id, overwrite_type) = match overwrite.target {
+			PermissionOverwriteTarget::Role(id) => (*id, 0),
+			PermissionOverwriteTarget::Member(id) => (*id, 1),
+			PermissionOverwriteTarget::Unknown { id, overwrite_type } => (id, overwrite_type),
+		};
+		Self {
+			id,
+			overwrite_type,
+			allow: overwrite.allow,
+			deny: overwrite.deny,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Guild {
 	pub id: GuildId,
 	pub name: String,
@@ -457,7 +600,7 @@ pub struct Guild {
 	pub owner: Option<bool>,
 	pub owner_id: UserId,
 	#[serde(default)]
-	pub permissions: Option<u64>,
+	pub permissions: Option<Permissions>,
 	pub region: String,
 	pub afk_channel_id: Option<ChannelId>,
 	pub afk_timeout: u64,
@@ -469,7 +612,8 @@ pub struct Guild {
 	pub default_message_notifications: u8,
 	pub explicit_content_filter: u8,
 	pub roles: Vec<Role>,
-	// pub emojis: Vec<Emoji>,
+	#[serde(default)]
+	pub emojis: Vec<Emoji>,
 	// pub features: Vec<String>,
 	pub mfa_level: u8,
 	pub application_id: Option<ApplicationId>,
@@ -509,22 +653,94 @@ pub struct Guild {
 	#[serde(default)]
 	pub approximate_presence_count: Option<u16>,
 	// welcome_screen
+	#[serde(default)]
+	pub stickers: Vec<Sticker>,
+}
+
+impl Guild {
+	/// Permissions of a member in the guild, before channel overwrites are applied.
+	/// The user id is passed separately since `member.user` is missing from
+	/// message and interaction payloads.
+	pub fn base_permissions(&self, user_id: UserId, member: &Member) -> Permissions {
+		if user_id == self.owner_id {
+			return Permissions::all();
+		}
+
+		let everyone = *self.id;
+		let permissions = self
+			.roles
+			.iter()
+			.filter(|r| *r.id == everyone || member.roles.contains(&r.id))
+			.fold(Permissions::empty(), |p, r| p | r.permissions);
+
+		if permissions.contains(Permissions::ADMINISTRATOR) {
+			Permissions::all()
+		} else {
+			permissions
+		}
+	}
+
+	/// Effective permissions of a member in a channel of this guild
+	pub fn channel_permissions(
+		&self,
+		user_id: UserId,
+		member: &Member,
+		channel: &Channel,
+	) -> Permissions {
+		let mut permissions = self.base_permissions(user_id, member);
+		if permissions.contains(Permissions::ADMINISTRATOR) {
+			return permissions;
+		}
+
+		let overwrites = &channel.permission_overwrites;
+		let everyone = RoleId::from(*self.id);
+		if let Some(o) = overwrites
+			.iter()
+			.find(|o| o.target == PermissionOverwriteTarget::Role(everyone))
+		{
+			permissions = (permissions - o.deny) | o.allow;
+		}
+
+		let (allow, deny) = overwrites
+			.iter()
+			.filter(|o| match o.target {
+				PermissionOverwriteTarget::Role(id) => id != everyone && member.roles.contains(&id),
+				_ => false,
+			})
+			.fold((Permissions::empty(), Permissions::empty()), |(a, d), o| {
+				(a | o.allow, d | o.deny)
+			});
+		permissions = (permissions - deny) | allow;
+
+		if let Some(o) = overwrites
+			.iter()
+			.find(|o| o.target == PermissionOverwriteTarget::Member(user_id))
+		{
+			permissions = (permissions - o.deny) | o.allow;
+		}
+
+		if member.is_timed_out() {
+			permissions &= Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY;
+		}
+
+		permissions
+	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Role {
 	pub id: RoleId,
 	pub name: String,
 	pub color: Color,
 	pub hoist: bool,
 	pub position: u16,
-	pub permissions: String,
+	pub permissions: Permissions,
 	pub managed: bool,
 	pub mentionable: bool,
 	// tags
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Member {
 	#[serde(default)]
 	pub user: Option<User>,
@@ -539,7 +755,22 @@ pub struct Member {
 	#[serde(default)]
 	pub pending: Option<bool>,
 	#[serde(default)]
-	pub permissions: Option<String>,
+	pub permissions: Option<Permissions>,
+	#[serde(default)]
+	pub communication_disabled_until: Option<DateTime>,
+}
+
+impl Member {
+	pub fn user_id(&self) -> Option<UserId> {
+		self.user.as_ref().map(|u| u.id)
+	}
+
+	pub fn is_timed_out(&self) -> bool {
+		match &self.communication_disabled_until {
+			Some(until) => *until > DateTime::now(),
+			None => false,
+		}
+	}
 }
 
 impl fmt::Display for Member {
@@ -555,7 +786,7 @@ impl fmt::Display for Member {
 	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct User {
 	pub id: UserId,
 	pub username: String,
@@ -601,7 +832,7 @@ impl fmt::Display for User {
 	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Message {
 	pub id: MessageId,
 	pub channel_id: ChannelId,
@@ -625,15 +856,17 @@ pub struct Message {
 	// mention_roles
 	// #[serde(default)]
 	// pub mention_channels: Vec<ChannelMention>,
-	// attachments
-	// embeds
+	#[serde(default)]
+	pub attachments: Vec<Attachment>,
+	#[serde(default)]
+	pub embeds: Vec<Embed>,
 	#[serde(default)]
 	pub reactions: Vec<Reaction>,
 	// nonce
 	#[serde(default)]
 	pub pinned: bool,
 	#[serde(default)]
-	pub webhook_id: Option<Snowflake>,
+	pub webhook_id: Option<WebhookId>,
 	#[serde(rename = "type")]
 	pub message_type: MessageType,
 	#[serde(default)]
@@ -642,14 +875,124 @@ pub struct Message {
 	pub components: Vec<Component>,
 	// activity
 	// application
-	// message_reference
+	#[serde(default)]
+	pub message_reference: Option<MessageReference>,
+	// Contents of forwarded messages
+	#[serde(default)]
+	pub message_snapshots: Vec<MessageSnapshot>,
 	// flags
-	// referenced_message
+	// Only for replies, `None` if the referenced message was deleted
+	#[serde(default)]
+	pub referenced_message: Option<Box<Message>>,
 	// thread
 	// sticker_items
 	// stickers
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReference {
+	#[serde(rename = "type", default)]
+	pub reference_type: MessageReferenceType,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub message_id: Option<MessageId>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub channel_id: Option<ChannelId>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub guild_id: Option<GuildId>,
+	// Error instead of sending a normal message if the referenced message is gone
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub fail_if_not_exists: Option<bool>,
+}
+
+impl MessageReference {
+	pub fn forward(message: &Message) -> Self {
+		Self {
+			reference_type: MessageReferenceType::Forward,
+			..message.into()
+		}
+	}
+}
+
+impl From<&Message> for MessageReference {
+	fn from(message: &Message) -> Self {
+		Self {
+			reference_type: MessageReferenceType::Default,
+			message_id: Some(message.id),
+			channel_id: Some(message.channel_id),
+			guild_id: message.guild_id,
+			fail_if_not_exists: None,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageSnapshot {
+	pub message: SnapshotMessage,
+}
+
+// Subset of the fields of a forwarded message
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SnapshotMessage {
+	#[serde(rename = "type")]
+	pub message_type: MessageType,
+	pub content: String,
+	#[serde(default)]
+	pub embeds: Vec<Embed>,
+	#[serde(default)]
+	pub attachments: Vec<Attachment>,
+	pub timestamp: DateTime,
+	pub edited_timestamp: Option<DateTime>,
+	#[serde(default)]
+	pub flags: Option<MessageFlags>,
+	#[serde(default)]
+	pub mentions: Vec<User>,
+	#[serde(default)]
+	pub mention_roles: Vec<RoleId>,
+	#[serde(default)]
+	pub components: Vec<Component>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Attachment {
+	pub id: Snowflake,
+	pub filename: String,
+	#[serde(default)]
+	pub title: Option<String>,
+	#[serde(default)]
+	pub description: Option<String>,
+	#[serde(default)]
+	pub content_type: Option<String>,
+	pub size: u64,
+	pub url: String,
+	pub proxy_url: String,
+	// Only for images and videos
+	#[serde(default)]
+	pub height: Option<u32>,
+	#[serde(default)]
+	pub width: Option<u32>,
+	#[serde(default)]
+	pub ephemeral: bool,
+	// Only for voice messages
+	#[serde(default)]
+	pub duration_secs: Option<f64>,
+	// Base64 encoded bytearray of the voice message waveform
+	#[serde(default)]
+	pub waveform: Option<String>,
+}
+
+impl Attachment {
+	pub fn is_voice_message(&self) -> bool {
+		self.waveform.is_some()
+	}
+}
+
+impl Message {
+	// Mentions, emoji and other tags in the content
+	pub fn tokens(&self) -> markup::Tokens<'_> {
+		markup::tokens(&self.content)
+	}
+}
+
 impl fmt::Display for Message {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		fmt::Display::fmt(&self.content, f)
@@ -662,7 +1005,7 @@ impl From<&Message> for ChannelId {
 	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MessageInteraction {
 	pub id: InteractionId,
 	#[serde(rename = "type")]
@@ -675,7 +1018,9 @@ pub struct MessageInteraction {
 pub struct Embed {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub title: Option<CowString>,
-	// type
+	// Always "rich" for bot embeds
+	#[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+	pub embed_type: Option<CowString>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub description: Option<CowString>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
@@ -684,27 +1029,57 @@ pub struct Embed {
 	pub timestamp: Option<chrono::DateTime<Utc>>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub color: Option<Color>,
-	// footer
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub footer: Option<EmbedFooter>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub image: Option<EmbedImage>,
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub thumbnail: Option<EmbedThumbnail>,
-	// video
-	// provider
-	// author
-	// fields
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub video: Option<EmbedVideo>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub provider: Option<EmbedProvider>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub author: Option<EmbedAuthor>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub fields: Vec<EmbedField>,
+}
+
+impl Default for Embed {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 impl Embed {
+	pub const TITLE_LIMIT: usize = 256;
+	pub const DESCRIPTION_LIMIT: usize = 4096;
+	pub const FIELD_COUNT_LIMIT: usize = 25;
+	pub const FIELD_NAME_LIMIT: usize = 256;
+	pub const FIELD_VALUE_LIMIT: usize = 1024;
+	pub const FOOTER_LIMIT: usize = 2048;
+	pub const AUTHOR_LIMIT: usize = 256;
+	// Number of embeds in a single message
+	pub const COUNT_LIMIT: usize = 10;
+	// Combined length of the title, description, field names and values,
+	// footer text and author name, across all embeds of a message
+	pub const TOTAL_LIMIT: usize = 6000;
+
 	pub fn new() -> Self {
 		Self {
 			title: None,
+			embed_type: None,
 			description: None,
 			url: None,
 			timestamp: None,
 			color: None,
+			footer: None,
 			image: None,
 			thumbnail: None,
+			video: None,
+			provider: None,
+			author: None,
+			fields: Vec::new(),
 		}
 	}
 
@@ -736,35 +1111,297 @@ impl Embed {
 	pub fn image<T: Into<CowString>>(mut self, url: T) -> Self {
 		self.image = Some(EmbedImage {
 			url: Some(url.into()),
+			proxy_url: None,
+			height: None,
+			width: None,
+		});
+		self
+	}
+
+	pub fn thumbnail<T: Into<CowString>>(mut self, url: T) -> Self {
+		self.thumbnail = Some(EmbedThumbnail {
+			url: Some(url.into()),
+			proxy_url: None,
 			height: None,
 			width: None,
 		});
 		self
 	}
+
+	pub fn footer<T: Into<CowString>>(mut self, text: T) -> Self {
+		self.footer = Some(EmbedFooter {
+			text: text.into(),
+			icon_url: None,
+			proxy_icon_url: None,
+		});
+		self
+	}
+
+	pub fn author<T: Into<CowString>>(mut self, name: T) -> Self {
+		self.author = Some(EmbedAuthor {
+			name: name.into(),
+			url: None,
+			icon_url: None,
+			proxy_icon_url: None,
+		});
+		self
+	}
+
+	pub fn field<N, V>(mut self, name: N, value: V, inline: bool) -> Self
+	where
+		N: Into<CowString>,
+		V: Into<CowString>,
+	{
+		self.fields.push(EmbedField {
+			name: name.into(),
+			value: value.into(),
+			inline,
+		});
+		self
+	}
+
+	// Number of characters counting towards `TOTAL_LIMIT`
+	pub fn text_len(&self) -> usize {
+		let len = |s: &Option<CowString>| s.as_deref().map_or(0, chars);
+		len(&self.title)
+			+ len(&self.description)
+			+ self
+				.fields
+				.iter()
+				.map(|f| chars(&f.name) + chars(&f.value))
+				.sum::<usize>()
+			+ self.footer.as_ref().map_or(0, |f| chars(&f.text))
+			+ self.author.as_ref().map_or(0, |a| chars(&a.name))
+	}
+
+	// Discord rejects embeds without any content
+	pub fn is_empty(&self) -> bool {
+		self.title.is_none()
+			&& self.description.is_none()
+			&& self.url.is_none()
+			&& self.timestamp.is_none()
+			&& self.color.is_none()
+			&& self.footer.is_none()
+			&& self.image.is_none()
+			&& self.thumbnail.is_none()
+			&& self.video.is_none()
+			&& self.provider.is_none()
+			&& self.author.is_none()
+			&& self.fields.is_empty()
+	}
+
+	// Check the embed against Discord's limits before sending it
+	pub fn validate(&self) -> Result<(), EmbedError> {
+		if let Some(len) = self.title.as_deref().map(chars) {
+			if len > Self::TITLE_LIMIT {
+				return Err(EmbedError::TitleTooLong(len));
+			}
+		}
+		if let Some(len) = self.description.as_deref().map(chars) {
+			if len > Self::DESCRIPTION_LIMIT {
+				return Err(EmbedError::DescriptionTooLong(len));
+			}
+		}
+		if self.fields.len() > Self::FIELD_COUNT_LIMIT {
+			return Err(EmbedError::TooManyFields(self.fields.len()));
+		}
+		for (index, field) in self.fields.iter().enumerate() {
+			let len = chars(&field.name);
+			if len > Self::FIELD_NAME_LIMIT {
+				return Err(EmbedError::FieldNameTooLong { index, len });
+			}
+			let len = chars(&field.value);
+			if len > Self::FIELD_VALUE_LIMIT {
+				return Err(EmbedError::FieldValueTooLong { index, len });
+			}
+		}
+		if let Some(len) = self.footer.as_ref().map(|f| chars(&f.text)) {
+			if len > Self::FOOTER_LIMIT {
+				return Err(EmbedError::FooterTooLong(len));
+			}
+		}
+		if let Some(len) = self.author.as_ref().map(|a| chars(&a.name)) {
+			if len > Self::AUTHOR_LIMIT {
+				return Err(EmbedError::AuthorNameTooLong(len));
+			}
+		}
+		let len = self.text_len();
+		if len > Self::TOTAL_LIMIT {
+			return Err(EmbedError::TooLong(len));
+		}
+		Ok(())
+	}
+
+	// Check the embeds of a message, `TOTAL_LIMIT` applies to all of them combined
+	pub fn validate_all(embeds: &[Embed]) -> Result<(), EmbedError> {
+		if embeds.len() > Self::COUNT_LIMIT {
+			return Err(EmbedError::TooManyEmbeds(embeds.len()));
+		}
+		for embed in embeds {
+			embed.validate()?;
+		}
+		let len = embeds.iter().map(Embed::text_len).sum();
+		if len > Self::TOTAL_LIMIT {
+			return Err(EmbedError::TooLong(len));
+		}
+		Ok(())
+	}
+}
+
+fn chars(s: &str) -> usize {
+	s.chars().count()
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EmbedError {
+	TitleTooLong(usize),
+	DescriptionTooLong(usize),
+	TooManyFields(usize),
+	FieldNameTooLong { index: usize, len: usize },
+	FieldValueTooLong { index: usize, len: usize },
+	FooterTooLong(usize),
+	AuthorNameTooLong(usize),
+	TooLong(usize),
+	TooManyEmbeds(usize),
+}
+
+impl fmt::Display for EmbedError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			EmbedError::TitleTooLong(len) => write!(
+				f,
+				"Embed title is {} characters, the limit is {}",
+				len,
+				Embed::TITLE_LIMIT
+			),
+			EmbedError::DescriptionTooLong(len) => write!(
+				f,
+				"Embed description is {} characters, the limit is {}",
+				len,
+				Embed::DESCRIPTION_LIMIT
+			),
+			EmbedError::TooManyFields(count) => write!(
+				f,
+				"Embed has {} fields, the limit is {}",
+				count,
+				Embed::FIELD_COUNT_LIMIT
+			),
+			EmbedError::FieldNameTooLong { index, len } => write!(
+				f,
+				"Embed field {} name is {} characters, the limit is {}",
+				index,
+				len,
+				Embed::FIELD_NAME_LIMIT
+			),
+			EmbedError::FieldValueTooLong { index, len } => write!(
+				f,
+				"Embed field {} value is {} characters, the limit is {}",
+				index,
+				len,
+				Embed::FIELD_VALUE_LIMIT
+			),
+			EmbedError::FooterTooLong(len) => write!(
+				f,
+				"Embed footer is {} characters, the limit is {}",
+				len,
+				Embed::FOOTER_LIMIT
+			),
+			EmbedError::AuthorNameTooLong(len) => write!(
+				f,
+				"Embed author name is {} characters, the limit is {}",
+				len,
+				Embed::AUTHOR_LIMIT
+			),
+			EmbedError::TooLong(len) => write!(
+				f,
+				"Embed is {} characters in total, the limit is {}",
+				len,
+				Embed::TOTAL_LIMIT
+			),
+			EmbedError::TooManyEmbeds(count) => write!(
+				f,
+				"Message has {} embeds, the limit is {}",
+				count,
+				Embed::COUNT_LIMIT
+			),
+		}
+	}
+}
+
+impl std::error::Error for EmbedError {}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EmbedFooter {
+	pub text: CowString,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub icon_url: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub proxy_icon_url: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedImage {
 	#[serde(default)]
 	pub url: Option<CowString>,
-	// #[serde(default)]
-	// pub proxy_url: Option<String>,
-	#[serde(default)]
-	pub height: Option<u16>,
-	#[serde(default)]
-	pub width: Option<u16>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub proxy_url: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub height: Option<u32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub width: Option<u32>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EmbedThumbnail {
 	#[serde(default)]
 	pub url: Option<CowString>,
-	// #[serde(default)]
-	// pub proxy_url: Option<String>,
-	// #[serde(default)]
-	// pub height: Option<u32>,
-	// #[serde(default)]
-	// pub width: Option<u32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub proxy_url: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub height: Option<u32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub width: Option<u32>,
+}
+
+// Can't be set by bots
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EmbedVideo {
+	#[serde(default)]
+	pub url: Option<String>,
+	#[serde(default)]
+	pub proxy_url: Option<String>,
+	#[serde(default)]
+	pub height: Option<u32>,
+	#[serde(default)]
+	pub width: Option<u32>,
+}
+
+// Can't be set by bots
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EmbedProvider {
+	#[serde(default)]
+	pub name: Option<String>,
+	#[serde(default)]
+	pub url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EmbedAuthor {
+	pub name: CowString,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub url: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub icon_url: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub proxy_icon_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EmbedField {
+	pub name: CowString,
+	pub value: CowString,
+	#[serde(default)]
+	pub inline: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -774,7 +1411,7 @@ pub struct Reaction {
 	pub emoji: PartialEmoji,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ApplicationCommand {
 	pub id: Snowflake,
 	pub application_id: ApplicationId,
@@ -784,6 +1421,31 @@ pub struct ApplicationCommand {
 	pub options: Vec<ApplicationCommandOption>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildApplicationCommandPermissions {
+	// Command id, or the application id if these apply to all commands
+	pub id: Snowflake,
+	pub application_id: ApplicationId,
+	pub guild_id: GuildId,
+	pub permissions: Vec<ApplicationCommandPermission>,
+}
+
+impl GuildApplicationCommandPermissions {
+	pub fn applies_to_all_commands(&self) -> bool {
+		self.id == u64::from(self.application_id)
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApplicationCommandPermission {
+	// Role, user or channel id; the guild id stands for @everyone and
+	// guild id - 1 for all channels
+	pub id: Snowflake,
+	#[serde(rename = "type")]
+	pub permission_type: ApplicationCommandPermissionType,
+	pub permission: bool,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ApplicationCommandOption {
 	#[serde(rename = "type")]
@@ -804,7 +1466,7 @@ pub struct ApplicationCommandOptionChoice {
 	pub value: CowString,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Interaction {
 	pub id: InteractionId,
 	pub application_id: Snowflake,
@@ -823,6 +1485,8 @@ pub struct Interaction {
 	pub version: u8,
 	#[serde(default)]
 	pub message: Option<Message>,
+	#[serde(default)]
+	pub app_permissions: Option<Permissions>,
 }
 
 impl Interaction {
@@ -835,7 +1499,7 @@ impl Interaction {
 	}
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct InteractionData {
 	#[serde(default)]
 	pub id: Option<Snowflake>,
@@ -851,7 +1515,7 @@ pub struct InteractionData {
 	pub component_type: Option<ComponentType>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct InteractionDataOption {
 	pub name: String,
 	#[serde(default)]
@@ -860,7 +1524,7 @@ pub struct InteractionDataOption {
 	pub options: Vec<InteractionDataOption>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VoiceState {
 	#[serde(default)]
 	pub guild_id: Option<GuildId>,
@@ -882,18 +1546,563 @@ pub struct VoiceState {
 	pub request_to_speak_timestamp: Option<DateTime>,
 }
 
-#[derive(Clone, Debug, Serialize)]
-pub struct AllowedMentions {
-	#[serde(skip_serializing_if = "Option::is_none")]
-	pub parse: Option<Vec<CowString>>,
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildScheduledEvent {
+	pub id: Snowflake,
+	pub guild_id: GuildId,
+	pub channel_id: Option<ChannelId>,
+	#[serde(default)]
+	pub creator_id: Option<UserId>,
+	pub name: String,
+	#[serde(default)]
+	pub description: Option<String>,
+	pub scheduled_start_time: DateTime,
+	pub scheduled_end_time: Option<DateTime>,
+	pub privacy_level: PrivacyLevel,
+	pub status: GuildScheduledEventStatus,
+	pub entity_type: GuildScheduledEventEntityType,
+	pub entity_id: Option<Snowflake>,
+	pub entity_metadata: Option<EntityMetadata>,
+	#[serde(default)]
+	pub creator: Option<User>,
+	#[serde(default)]
+	pub user_count: Option<u32>,
+	#[serde(default)]
+	pub image: Option<String>,
+	#[serde(default)]
+	pub recurrence_rule: Option<RecurrenceRule>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EntityMetadata {
+	// Required for external events
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub location: Option<CowString>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RecurrenceRule {
+	pub start: DateTime,
+	#[serde(default)]
+	pub end: Option<DateTime>,
+	pub frequency: RecurrenceFrequency,
+	pub interval: u16,
+	#[serde(default)]
+	pub by_weekday: Option<Vec<Weekday>>,
+	#[serde(default)]
+	pub by_n_weekday: Option<Vec<NWeekday>>,
+	#[serde(default)]
+	pub by_month: Option<Vec<Month>>,
+	#[serde(default)]
+	pub by_month_day: Option<Vec<u8>>,
+	#[serde(default)]
+	pub by_year_day: Option<Vec<u16>>,
+	#[serde(default)]
+	pub count: Option<u32>,
+}
+
+impl RecurrenceRule {
+	pub fn new(start: DateTime, frequency: RecurrenceFrequency, interval: u16) -> Self {
+		Self {
+			start,
+			end: None,
+			frequency,
+			interval,
+			by_weekday: None,
+			by_n_weekday: None,
+			by_month: None,
+			by_month_day: None,
+			by_year_day: None,
+			count: None,
+		}
+	}
+}
+
+// The n-th weekday of the month, e.g. the second Tuesday
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NWeekday {
+	pub n: u8,
+	pub day: Weekday,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AutoModerationRule {
+	pub id: Snowflake,
+	pub guild_id: GuildId,
+	pub name: String,
+	pub creator_id: UserId,
+	pub event_type: AutoModerationEventType,
+	pub trigger_type: TriggerType,
+	pub trigger_metadata: TriggerMetadata,
+	pub actions: Vec<AutoModerationAction>,
+	pub enabled: bool,
+	pub exempt_roles: Vec<RoleId>,
+	pub exempt_channels: Vec<ChannelId>,
+}
+
+// Which fields apply depends on the trigger type
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TriggerMetadata {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub keyword_filter: Option<Vec<CowString>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub regex_patterns: Option<Vec<CowString>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub presets: Option<Vec<KeywordPresetType>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub allow_list: Option<Vec<CowString>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub mention_total_limit: Option<u8>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub mention_raid_protection_enabled: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AutoModerationAction {
+	#[serde(rename = "type")]
+	pub action_type: AutoModerationActionType,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub metadata: Option<ActionMetadata>,
+}
+
+impl AutoModerationAction {
+	pub fn block_message() -> Self {
+		Self {
+			action_type: AutoModerationActionType::BlockMessage,
+			metadata: None,
+		}
+	}
+
+	pub fn send_alert_message(channel_id: ChannelId) -> Self {
+		Self {
+			action_type: AutoModerationActionType::SendAlertMessage,
+			metadata: Some(ActionMetadata {
+				channel_id: Some(channel_id),
+				..Default::default()
+			}),
+		}
+	}
+
+	// Up to 4 weeks
+	pub fn timeout(duration_seconds: u32) -> Self {
+		Self {
+			action_type: AutoModerationActionType::Timeout,
+			metadata: Some(ActionMetadata {
+				duration_seconds: Some(duration_seconds),
+				..Default::default()
+			}),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ActionMetadata {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub channel_id: Option<ChannelId>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub duration_seconds: Option<u32>,
+	// Shown to the member when their message is blocked
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub custom_message: Option<CowString>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Invite {
+	pub code: String,
+	#[serde(default)]
+	pub guild: Option<PartialGuild>,
+	pub channel: Option<Channel>,
+	#[serde(default)]
+	pub inviter: Option<User>,
+	#[serde(default)]
+	pub target_type: Option<InviteTargetType>,
+	#[serde(default)]
+	pub target_user: Option<User>,
+	// target_application
+	#[serde(default)]
+	pub approximate_presence_count: Option<u32>,
+	#[serde(default)]
+	pub approximate_member_count: Option<u32>,
+	#[serde(default)]
+	pub expires_at: Option<DateTime>,
+	// Invite metadata, only returned by the guild and channel invite endpoints
+	#[serde(default)]
+	pub uses: Option<u32>,
+	#[serde(default)]
+	pub max_uses: Option<u32>,
+	#[serde(default)]
+	pub max_age: Option<u32>,
+	#[serde(default)]
+	pub temporary: Option<bool>,
+	#[serde(default)]
+	pub created_at: Option<DateTime>,
+}
+
+impl Invite {
+	pub fn url(&self) -> String {
+		format!("https://discord.gg/{}", self.code)
+	}
+
+	// Whether `max_uses` has been reached, `None` without invite metadata
+	pub fn is_exhausted(&self) -> Option<bool> {
+		match (self.uses, self.max_uses) {
+			(Some(_), Some(0)) => Some(false),
+			(Some(uses), Some(max_uses)) => Some(uses >= max_uses),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PartialGuild {
+	pub id: GuildId,
+	pub name: String,
+	pub icon: Option<String>,
+	#[serde(default)]
+	pub splash: Option<String>,
+	#[serde(default)]
+	pub banner: Option<String>,
+	#[serde(default)]
+	pub description: Option<String>,
+	#[serde(default)]
+	pub verification_level: Option<u8>,
+	#[serde(default)]
+	pub vanity_url_code: Option<String>,
+	#[serde(default)]
+	pub nsfw_level: Option<u8>,
+	#[serde(default)]
+	pub premium_subscription_count: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Webhook {
+	pub id: WebhookId,
+	#[serde(rename = "type")]
+	pub webhook_type: WebhookType,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	pub channel_id: Option<ChannelId>,
+	#[serde(default)]
+	pub user: Option<User>,
+	pub name: Option<String>,
+	pub avatar: Option<String>,
+	// Only for incoming webhooks
+	#[serde(default)]
+	pub token: Option<String>,
+	pub application_id: Option<ApplicationId>,
+	// Only for channel follower webhooks
+	#[serde(default)]
+	pub source_guild: Option<PartialGuild>,
+	// source_channel
+	#[serde(default)]
+	pub url: Option<String>,
+}
+
+impl Webhook {
+	pub fn is_incoming(&self) -> bool {
+		self.webhook_type == WebhookType::Incoming
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StageInstance {
+	pub id: Snowflake,
+	pub guild_id: GuildId,
+	pub channel_id: ChannelId,
+	pub topic: String,
+	pub privacy_level: PrivacyLevel,
+	pub discoverable_disabled: bool,
+	pub guild_scheduled_event_id: Option<Snowflake>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Integration {
+	pub id: Snowflake,
+	pub name: String,
+	// twitch, youtube, discord or guild_subscription
+	#[serde(rename = "type")]
+	pub integration_type: String,
+	pub enabled: bool,
+	#[serde(default)]
+	pub syncing: Option<bool>,
+	#[serde(default)]
+	pub role_id: Option<RoleId>,
+	#[serde(default)]
+	pub enable_emoticons: Option<bool>,
+	#[serde(default)]
+	pub expire_behavior: Option<IntegrationExpireBehavior>,
+	#[serde(default)]
+	pub expire_grace_period: Option<u32>,
+	#[serde(default)]
+	pub user: Option<User>,
+	pub account: IntegrationAccount,
+	#[serde(default)]
+	pub synced_at: Option<DateTime>,
+	#[serde(default)]
+	pub subscriber_count: Option<u32>,
+	#[serde(default)]
+	pub revoked: Option<bool>,
+	#[serde(default)]
+	pub application: Option<IntegrationApplication>,
+	#[serde(default)]
+	pub scopes: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IntegrationAccount {
+	// Not a snowflake for twitch and youtube accounts
+	pub id: String,
+	pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IntegrationApplication {
+	pub id: ApplicationId,
+	pub name: String,
+	pub icon: Option<String>,
+	pub description: String,
+	#[serde(default)]
+	pub bot: Option<User>,
+}
+
+// Once set on a message, only the mentions listed here will ping
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AllowedMentions {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub parse: Option<Vec<MentionType>>,
+	// Mutually exclusive with `MentionType::Users` in `parse`
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub users: Vec<UserId>,
+	// Mutually exclusive with `MentionType::Roles` in `parse`
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub roles: Vec<RoleId>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub replied_user: Option<bool>,
+}
+
+impl AllowedMentions {
+	// Discord rejects more ids than this per type
+	pub const ID_LIMIT: usize = 100;
+
+	pub fn none() -> Self {
+		Self {
+			parse: Some(Vec::new()),
+			..Default::default()
+		}
+	}
+
+	pub fn all() -> Self {
+		Self {
+			parse: Some(vec![
+				MentionType::Users,
+				MentionType::Roles,
+				MentionType::Everyone,
+			]),
+			..Default::default()
+		}
+	}
+
+	pub fn only_users<I, T>(users: I) -> Self
+	where
+		I: IntoIterator<Item = T>,
+		T: Into<UserId>,
+	{
+		Self {
+			users: users.into_iter().map(Into::into).collect(),
+			..Self::none()
+		}
+	}
+
+	pub fn only_roles<I, T>(roles: I) -> Self
+	where
+		I: IntoIterator<Item = T>,
+		T: Into<RoleId>,
+	{
+		Self {
+			roles: roles.into_iter().map(Into::into).collect(),
+			..Self::none()
+		}
+	}
+
+	// Allow exactly the user, role and everyone mentions that occur in `content`
+	pub fn from_content(content: &str) -> Self {
+		let mut mentions = Self::none();
+		for token in markup::tokens(content) {
+			match token.kind {
+				TokenKind::User(id) => mentions.push_user(id),
+				TokenKind::Role(id) => mentions.push_role(id),
+				TokenKind::Everyone | TokenKind::Here => {
+					mentions.parse = Some(vec![MentionType::Everyone])
+				}
+				_ => {}
+			}
+		}
+		mentions
+	}
+
+	pub fn user<T: Into<UserId>>(mut self, user: T) -> Self {
+		self.push_user(user.into());
+		self
+	}
+
+	pub fn role<T: Into<RoleId>>(mut self, role: T) -> Self {
+		self.push_role(role.into());
+		self
+	}
+
+	pub fn replied_user(mut self, replied_user: bool) -> Self {
+		self.replied_user = Some(replied_user);
+		self
+	}
+
+	fn push_user(&mut self, user: UserId) {
+		if !self.users.contains(&user) && self.users.len() < Self::ID_LIMIT {
+			self.users.push(user);
+		}
+	}
+
+	fn push_role(&mut self, role: RoleId) {
+		if !self.roles.contains(&role) && self.roles.len() < Self::ID_LIMIT {
+			self.roles.push(role);
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Activity {
+	pub name: CowString,
+	#[serde(rename = "type")]
+	pub activity_type: ActivityType,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub url: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub created_at: Option<u64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub timestamps: Option<ActivityTimestamps>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub application_id: Option<ApplicationId>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub details: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub emoji: Option<PartialEmoji>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub party: Option<ActivityParty>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub assets: Option<ActivityAssets>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub instance: Option<bool>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub flags: Option<ActivityFlags>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub buttons: Vec<ActivityButton>,
+}
+
+impl Activity {
+	pub fn new<T: Into<CowString>>(activity_type: ActivityType, name: T) -> Self {
+		Self {
+			name: name.into(),
+			activity_type,
+			url: None,
+			created_at: None,
+			timestamps: None,
+			application_id: None,
+			details: None,
+			state: None,
+			emoji: None,
+			party: None,
+			assets: None,
+			instance: None,
+			flags: None,
+			buttons: Vec::new(),
+		}
+	}
+
+	pub fn playing<T: Into<CowString>>(name: T) -> Self {
+		Self::new(ActivityType::Playing, name)
+	}
+
+	pub fn streaming<T: Into<CowString>, U: Into<CowString>>(name: T, url: U) -> Self {
+		Self::new(ActivityType::Streaming, name).url(url)
+	}
+
+	pub fn listening<T: Into<CowString>>(name: T) -> Self {
+		Self::new(ActivityType::Listening, name)
+	}
+
+	pub fn watching<T: Into<CowString>>(name: T) -> Self {
+		Self::new(ActivityType::Watching, name)
+	}
+
+	pub fn competing<T: Into<CowString>>(name: T) -> Self {
+		Self::new(ActivityType::Competing, name)
+	}
+
+	pub fn custom<T: Into<CowString>>(state: T) -> Self {
+		Self::new(ActivityType::Custom, "Custom Status").state(state)
+	}
+
+	pub fn url<T: Into<CowString>>(mut self, url: T) -> Self {
+		self.url = Some(url.into());
+		self
+	}
+
+	pub fn state<T: Into<CowString>>(mut self, state: T) -> Self {
+		self.state = Some(state.into());
+		self
+	}
+
+	pub fn emoji<T: Into<PartialEmoji>>(mut self, emoji: T) -> Self {
+		self.emoji = Some(emoji.into());
+		self
+	}
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ActivityTimestamps {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub start: Option<u64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub end: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ActivityParty {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub id: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub size: Option<[u32; 2]>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ActivityAssets {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub large_image: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub large_text: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub small_image: Option<CowString>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub small_text: Option<CowString>,
+}
+
+// Bots send buttons with a url, but only receive their labels
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ActivityButton {
+	Link { label: CowString, url: CowString },
+	Label(CowString),
 }
 
-impl AllowedMentions {
-	pub fn none() -> Self {
-		Self {
-			parse: Some(Vec::new()),
-		}
-	}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ClientStatus {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub desktop: Option<Status>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub mobile: Option<Status>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub web: Option<Status>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -935,6 +2144,62 @@ pub struct SelectOption {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Emoji {
+	pub id: Option<Snowflake>,
+	pub name: Option<String>,
+	#[serde(default)]
+	pub roles: Vec<RoleId>,
+	#[serde(default)]
+	pub user: Option<User>,
+	#[serde(default)]
+	pub require_colons: bool,
+	#[serde(default)]
+	pub managed: bool,
+	#[serde(default)]
+	pub animated: bool,
+	// False when the guild lost the boosts for it, absent on partial emojis
+	#[serde(default)]
+	pub available: Option<bool>,
+}
+
+impl From<&Emoji> for PartialEmoji {
+	fn from(emoji: &Emoji) -> Self {
+		Self {
+			id: emoji.id,
+			name: emoji.name.clone().map(Into::into),
+			animated: emoji.animated,
+		}
+	}
+}
+
+impl fmt::Display for Emoji {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(&PartialEmoji::from(self), f)
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Sticker {
+	pub id: Snowflake,
+	#[serde(default)]
+	pub pack_id: Option<Snowflake>,
+	pub name: String,
+	pub description: Option<String>,
+	pub tags: String,
+	#[serde(rename = "type")]
+	pub sticker_type: StickerType,
+	pub format_type: StickerFormatType,
+	#[serde(default)]
+	pub available: Option<bool>,
+	#[serde(default)]
+	pub guild_id: Option<GuildId>,
+	#[serde(default)]
+	pub user: Option<User>,
+	#[serde(default)]
+	pub sort_value: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct PartialEmoji {
 	#[serde(default)]
 	pub id: Option<Snowflake>,
@@ -944,6 +2209,17 @@ pub struct PartialEmoji {
 	pub animated: bool,
 }
 
+impl fmt::Display for PartialEmoji {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name = self.name.as_deref().unwrap_or("_");
+		match self.id {
+			Some(id) if self.animated => write!(f, "<a:{}:{}>", name, id),
+			Some(id) => write!(f, "<:{}:{}>", name, id),
+			None => fmt::Display::fmt(name, f),
+		}
+	}
+}
+
 impl From<&emoji::Emoji> for PartialEmoji {
 	fn from(emoji: &emoji::Emoji) -> Self {
 		Self {
@@ -1185,6 +2461,86 @@ impl<'de> serde::Deserialize<'de> for Intents {
 	}
 }
 
+bitflags::bitflags! {
+	#[repr(transparent)]
+	#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+	pub struct Permissions: u64 {
+		const CREATE_INSTANT_INVITE = 1 << 0;
+		const KICK_MEMBERS = 1 << 1;
+		const BAN_MEMBERS = 1 << 2;
+		const ADMINISTRATOR = 1 << 3;
+		const MANAGE_CHANNELS = 1 << 4;
+		const MANAGE_GUILD = 1 << 5;
+		const ADD_REACTIONS = 1 << 6;
+		const VIEW_AUDIT_LOG = 1 << 7;
+		const PRIORITY_SPEAKER = 1 << 8;
+		const STREAM = 1 << 9;
+		const VIEW_CHANNEL = 1 << 10;
+		const SEND_MESSAGES = 1 << 11;
+		const SEND_TTS_MESSAGES = 1 << 12;
+		const MANAGE_MESSAGES = 1 << 13;
+		const EMBED_LINKS = 1 << 14;
+		const ATTACH_FILES = 1 << 15;
+		const READ_MESSAGE_HISTORY = 1 << 16;
+		const MENTION_EVERYONE = 1 << 17;
+		const USE_EXTERNAL_EMOJIS = 1 << 18;
+		const VIEW_GUILD_INSIGHTS = 1 << 19;
+		const CONNECT = 1 << 20;
+		const SPEAK = 1 << 21;
+		const MUTE_MEMBERS = 1 << 22;
+		const DEAFEN_MEMBERS = 1 << 23;
+		const MOVE_MEMBERS = 1 << 24;
+		const USE_VAD = 1 << 25;
+		const CHANGE_NICKNAME = 1 << 26;
+		const MANAGE_NICKNAMES = 1 << 27;
+		const MANAGE_ROLES = 1 << 28;
+		const MANAGE_WEBHOOKS = 1 << 29;
+		const MANAGE_GUILD_EXPRESSIONS = 1 << 30;
+		const USE_APPLICATION_COMMANDS = 1 << 31;
+		const REQUEST_TO_SPEAK = 1 << 32;
+		const MANAGE_EVENTS = 1 << 33;
+		const MANAGE_THREADS = 1 << 34;
+		const CREATE_PUBLIC_THREADS = 1 << 35;
+		const CREATE_PRIVATE_THREADS = 1 << 36;
+		const USE_EXTERNAL_STICKERS = 1 << 37;
+		const SEND_MESSAGES_IN_THREADS = 1 << 38;
+		const USE_EMBEDDED_ACTIVITIES = 1 << 39;
+		const MODERATE_MEMBERS = 1 << 40;
+		const VIEW_CREATOR_MONETIZATION_ANALYTICS = 1 << 41;
+		const USE_SOUNDBOARD = 1 << 42;
+		const CREATE_GUILD_EXPRESSIONS = 1 << 43;
+		const CREATE_EVENTS = 1 << 44;
+		const USE_EXTERNAL_SOUNDS = 1 << 45;
+		const SEND_VOICE_MESSAGES = 1 << 46;
+		const SEND_POLLS = 1 << 49;
+		const USE_EXTERNAL_APPS = 1 << 50;
+		const PIN_MESSAGES = 1 << 51;
+		const BYPASS_SLOWMODE = 1 << 52;
+	}
+}
+
+// Permissions can exceed 53 bits, so Discord sends them as a decimal string
+impl serde::Serialize for Permissions {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&self.bits().to_string())
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for Permissions {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_any(BitFlagsVisitor::retain())
+	}
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MentionType {
+	Users,
+	Roles,
+	// Both @everyone and @here
+	Everyone,
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Status {
@@ -1196,6 +2552,47 @@ pub enum Status {
 	Offline,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum ActivityType {
+	Playing = 0,
+	Streaming = 1,
+	Listening = 2,
+	Watching = 3,
+	Custom = 4,
+	Competing = 5,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+bitflags::bitflags! {
+	#[repr(transparent)]
+	#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+	pub struct ActivityFlags: u64 {
+		const INSTANCE = 1 << 0;
+		const JOIN = 1 << 1;
+		const SPECTATE = 1 << 2;
+		const JOIN_REQUEST = 1 << 3;
+		const SYNC = 1 << 4;
+		const PLAY = 1 << 5;
+		const PARTY_PRIVACY_FRIENDS = 1 << 6;
+		const PARTY_PRIVACY_VOICE_CHANNEL = 1 << 7;
+		const EMBEDDED = 1 << 8;
+	}
+}
+
+impl serde::Serialize for ActivityFlags {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_u64(self.bits())
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for ActivityFlags {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_u64(BitFlagsVisitor::new())
+	}
+}
+
 #[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
 #[repr(u8)]
 pub enum ChannelType {
@@ -1212,6 +2609,62 @@ pub enum ChannelType {
 	GuildStageVoice = 13,
 	GuildDirectory = 14,
 	GuildForum = 15,
+	GuildMedia = 16,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+impl ChannelType {
+	pub fn is_thread(&self) -> bool {
+		matches!(
+			self,
+			Self::GuildNewsThread | Self::GuildPublicThread | Self::GuildPrivateThread
+		)
+	}
+
+	// Channels that only contain threads, created as posts
+	pub fn is_forum_like(&self) -> bool {
+		matches!(self, Self::GuildForum | Self::GuildMedia)
+	}
+}
+
+bitflags::bitflags! {
+	#[repr(transparent)]
+	#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+	pub struct ChannelFlags: u64 {
+		const PINNED = 1 << 1;
+		const REQUIRE_TAG = 1 << 4;
+		const HIDE_MEDIA_DOWNLOAD_OPTIONS = 1 << 15;
+	}
+}
+
+impl serde::Serialize for ChannelFlags {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_u64(self.bits())
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for ChannelFlags {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_u64(BitFlagsVisitor::new())
+	}
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum SortOrderType {
+	LatestActivity = 0,
+	CreationDate = 1,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum ForumLayoutType {
+	NotSet = 0,
+	ListView = 1,
+	GalleryView = 2,
 	#[serde(other)]
 	Unknown = 255,
 }
@@ -1241,7 +2694,7 @@ bitflags::bitflags! {
 
 impl serde::Serialize for UserFlags {
 	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-		serializer.serialize_u64(self.bits() as u64)
+		serializer.serialize_u64(self.bits())
 	}
 }
 
@@ -1291,12 +2744,10 @@ pub enum MessageType {
 
 impl MessageType {
 	pub fn is_textual(&self) -> bool {
-		match self {
-			Self::Default | Self::Reply | Self::ApplicationCommand | Self::ThreadStarterMessage => {
-				true
-			}
-			_ => false,
-		}
+		matches!(
+			self,
+			Self::Default | Self::Reply | Self::ApplicationCommand | Self::ThreadStarterMessage
+		)
 	}
 }
 
@@ -1312,12 +2763,212 @@ bitflags::bitflags! {
 	}
 }
 
+impl serde::Serialize for MessageFlags {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_u64(self.bits())
+	}
+}
+
 impl<'de> serde::Deserialize<'de> for MessageFlags {
 	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
 		deserializer.deserialize_u64(BitFlagsVisitor::new())
 	}
 }
 
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum PrivacyLevel {
+	Public = 1,
+	GuildOnly = 2,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum GuildScheduledEventStatus {
+	Scheduled = 1,
+	Active = 2,
+	Completed = 3,
+	Canceled = 4,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum GuildScheduledEventEntityType {
+	StageInstance = 1,
+	Voice = 2,
+	External = 3,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum RecurrenceFrequency {
+	Yearly = 0,
+	Monthly = 1,
+	Weekly = 2,
+	Daily = 3,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum Weekday {
+	Monday = 0,
+	Tuesday = 1,
+	Wednesday = 2,
+	Thursday = 3,
+	Friday = 4,
+	Saturday = 5,
+	Sunday = 6,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum Month {
+	January = 1,
+	February = 2,
+	March = 3,
+	April = 4,
+	May = 5,
+	June = 6,
+	July = 7,
+	August = 8,
+	September = 9,
+	October = 10,
+	November = 11,
+	December = 12,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum AutoModerationEventType {
+	MessageSend = 1,
+	MemberUpdate = 2,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum TriggerType {
+	Keyword = 1,
+	Spam = 3,
+	KeywordPreset = 4,
+	MentionSpam = 5,
+	MemberProfile = 6,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum KeywordPresetType {
+	Profanity = 1,
+	SexualContent = 2,
+	Slurs = 3,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum AutoModerationActionType {
+	BlockMessage = 1,
+	SendAlertMessage = 2,
+	Timeout = 3,
+	BlockMemberInteraction = 4,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum InviteTargetType {
+	Stream = 1,
+	EmbeddedApplication = 2,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum WebhookType {
+	Incoming = 1,
+	ChannelFollower = 2,
+	Application = 3,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum IntegrationExpireBehavior {
+	RemoveRole = 0,
+	Kick = 1,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum ApplicationCommandPermissionType {
+	Role = 1,
+	User = 2,
+	Channel = 3,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum StickerType {
+	Standard = 1,
+	Guild = 2,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum StickerFormatType {
+	Png = 1,
+	Apng = 2,
+	Lottie = 3,
+	Gif = 4,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum MessageReferenceType {
+	#[default]
+	Default = 0,
+	Forward = 1,
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
+#[repr(u8)]
+pub enum ReactionType {
+	#[default]
+	Normal = 0,
+	Burst = 1,
+	#[serde(other)]
+	Unknown = 255,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize_repr, Eq, PartialEq, Serialize_repr)]
 #[repr(u8)]
 pub enum ApplicationCommandOptionType {
@@ -1439,6 +3090,90 @@ mod tests {
 		assert_tokens(&(Intents::GUILD_ALL).readable(), &[Token::U64(69631)]);
 	}
 
+	#[test]
+	fn permissions() {
+		let permissions = Permissions::VIEW_CHANNEL | Permissions::SEND_POLLS;
+		assert_tokens(&permissions.readable(), &[Token::Str("562949953422336")]);
+		let role = r#"{"id":"1","name":"@everyone","color":0,"hoist":false,"position":0,"permissions":"1099511630848","managed":false,"mentionable":false,"flags":0}"#;
+		let role: Role = serde_json::from_str(role).unwrap();
+		assert!(role.permissions.contains(Permissions::MODERATE_MEMBERS));
+		assert!(!role.permissions.contains(Permissions::ADMINISTRATOR));
+
+		// Bits we don't know about yet are kept when sending the role back
+		let unknown = (1u64 << 62) | Permissions::VIEW_CHANNEL.bits();
+		let json = format!(r#""{}""#, unknown);
+		let permissions: Permissions = serde_json::from_str(&json).unwrap();
+		assert_eq!(permissions.bits(), unknown);
+		assert!(permissions.contains(Permissions::VIEW_CHANNEL));
+		assert_eq!(serde_json::to_string(&permissions).unwrap(), json);
+	}
+
+	#[test]
+	fn channel_permissions() {
+		let guild = r#"{"id":"1","name":"guild","icon":null,"splash":null,"discovery_splash":null,"owner_id":"10","region":"",
+			"afk_channel_id":null,"afk_timeout":0,"verification_level":0,"default_message_notifications":0,"explicit_content_filter":0,
+			"roles":[{"id":"1","name":"@everyone","color":0,"hoist":false,"position":0,"permissions":"3072","managed":false,"mentionable":false},
+			{"id":"2","name":"mod","color":0,"hoist":false,"position":1,"permissions":"8192","managed":false,"mentionable":false},
+			{"id":"3","name":"admin","color":0,"hoist":false,"position":2,"permissions":"8","managed":false,"mentionable":false}],
+			"mfa_level":0,"application_id":null,"system_channel_id":null,"system_channel_flags":0,"rules_channel_id":null,
+			"vanity_url_code":null,"description":null,"banner":null,"premium_tier":0,"preferred_locale":"en-US","public_updates_channel_id":null}"#;
+		let guild: Guild = serde_json::from_str(guild).unwrap();
+		let channel = r#"{"id":"100","type":0,"permission_overwrites":[
+			{"id":"1","type":0,"allow":"0","deny":"2048"},
+			{"id":"2","type":0,"allow":"2048","deny":"0"},
+			{"id":"21","type":1,"allow":"0","deny":"1024"},
+			{"id":"20","type":9,"allow":"0","deny":"1024"}]}"#;
+		let channel: Channel = serde_json::from_str(channel).unwrap();
+		assert_eq!(
+			channel.permission_overwrites[3].target,
+			PermissionOverwriteTarget::Unknown {
+				id: 20.into(),
+				overwrite_type: 9
+			}
+		);
+		let json = serde_json::to_value(&channel.permission_overwrites[3]).unwrap();
+		assert_eq!(json["type"], 9);
+		// Members in message and interaction payloads have no user
+		let member = |id: u64, roles: &str, until: &str| -> (UserId, Member) {
+			let json = format!(
+				r#"{{"roles":{},"joined_at":"2023-01-01T00:00:00+00:00","deaf":false,"mute":false,"communication_disabled_until":{}}}"#,
+				roles, until
+			);
+			(id.into(), serde_json::from_str(&json).unwrap())
+		};
+
+		let everyone = member(20, "[]", "null");
+		assert_eq!(
+			guild.channel_permissions(everyone.0, &everyone.1, &channel),
+			Permissions::VIEW_CHANNEL
+		);
+		let moderator = member(20, r#"["2"]"#, "null");
+		assert_eq!(
+			guild.channel_permissions(moderator.0, &moderator.1, &channel),
+			Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES
+		);
+		let hidden = member(21, r#"["2"]"#, "null");
+		assert_eq!(
+			guild.channel_permissions(hidden.0, &hidden.1, &channel),
+			Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES
+		);
+		let timed_out = member(20, r#"["2"]"#, r#""2999-01-01T00:00:00+00:00""#);
+		assert_eq!(
+			guild.channel_permissions(timed_out.0, &timed_out.1, &channel),
+			Permissions::VIEW_CHANNEL
+		);
+		let admin = member(21, r#"["3"]"#, "null");
+		assert_eq!(
+			guild.channel_permissions(admin.0, &admin.1, &channel),
+			Permissions::all()
+		);
+		let owner = member(10, "[]", "null");
+		assert_eq!(
+			guild.channel_permissions(owner.0, &owner.1, &channel),
+			Permissions::all()
+		);
+	}
+
 	#[test]
 	fn user() {
 		let json = r#"{"verified":true,"username":"[DEV] Galaxy of Dreams","mfa_enabled":true,"id":"292738137426362368","global_name":null,"flags":0,"email":null,"discriminator":"6948","bot":true,"avatar":null}"#;
@@ -1450,4 +3185,80 @@ mod tests {
 		let json = r#"{"user":{"username":"anon","public_flags":4195072,"id":"1","global_name":"anon","display_name":"anon","discriminator":"0","bot":false,"avatar_decoration":null},"roles":[],"premium_since":null,"pending":false,"nick":null,"mute":false,"joined_at":"2023-01-01T00:00:00.000000+00:00","flags":0,"deaf":false,"communication_disabled_until":null,"avatar":null}"#;
 		let _member: Member = serde_json::from_str(json).unwrap();
 	}
+
+	#[test]
+	fn embed() {
+		let embed = Embed::new()
+			.title("Title")
+			.author("Author")
+			.footer("Footer")
+			.thumbnail("https://example.com/a.png")
+			.field("Name", "Value", true);
+		assert_eq!(embed.text_len(), 26);
+		assert_eq!(embed.validate(), Ok(()));
+		let json = serde_json::to_string(&embed).unwrap();
+		assert_eq!(
+			json,
+			r#"{"title":"Title","footer":{"text":"Footer"},"thumbnail":{"url":"https://example.com/a.png"},"author":{"name":"Author"},"fields":[{"name":"Name","value":"Value","inline":true}]}"#
+		);
+		assert!(Embed::new().is_empty());
+		assert!(!Embed::new().image("https://example.com/a.png").is_empty());
+
+		let embed = Embed::new().title("é".repeat(257));
+		assert_eq!(embed.validate(), Err(EmbedError::TitleTooLong(257)));
+		let embed = (0..26).fold(Embed::new(), |e, _| e.field("a", "b", false));
+		assert_eq!(embed.validate(), Err(EmbedError::TooManyFields(26)));
+		let embed = Embed::new().field("a", "b".repeat(1025), false);
+		assert_eq!(
+			embed.validate(),
+			Err(EmbedError::FieldValueTooLong {
+				index: 0,
+				len: 1025
+			})
+		);
+		let embed = Embed::new()
+			.description("a".repeat(4096))
+			.footer("b".repeat(2000));
+		assert_eq!(embed.validate(), Err(EmbedError::TooLong(6096)));
+
+		let embeds = vec![Embed::new().description("a".repeat(4000)); 2];
+		assert_eq!(embeds[0].validate(), Ok(()));
+		assert_eq!(Embed::validate_all(&embeds), Err(EmbedError::TooLong(8000)));
+		let embeds = vec![Embed::new().title("a"); 11];
+		assert_eq!(
+			Embed::validate_all(&embeds),
+			Err(EmbedError::TooManyEmbeds(11))
+		);
+		assert_eq!(Embed::validate_all(&embeds[..10]), Ok(()));
+	}
+
+	#[test]
+	fn allowed_mentions() {
+		let mentions =
+			AllowedMentions::from_content("<@1> <@!2> <@&3> <@1> <@x> <@&> <@4 @here <#5> <@6>");
+		assert_eq!(mentions.parse, Some(vec![MentionType::Everyone]));
+		assert_eq!(mentions.users, vec![1, 2, 6]);
+		assert_eq!(mentions.roles, vec![3]);
+
+		let json = serde_json::to_string(&AllowedMentions::from_content("hi <@1>")).unwrap();
+		assert_eq!(json, r#"{"parse":[],"users":["1"]}"#);
+		let json = serde_json::to_string(&AllowedMentions::all().replied_user(false)).unwrap();
+		assert_eq!(
+			json,
+			r#"{"parse":["users","roles","everyone"],"replied_user":false}"#
+		);
+		assert_eq!(
+			AllowedMentions::only_roles([RoleId::from(3)]),
+			AllowedMentions::from_content("<@&3>")
+		);
+	}
+
+	#[test]
+	fn invite() {
+		let json = r#"{"type":0,"code":"abc","guild":{"id":"1","name":"guild","icon":null,"splash":null,"banner":null,"description":null,"features":[],"verification_level":1,"vanity_url_code":null,"nsfw_level":0,"premium_subscription_count":2},"channel":{"id":"3","type":0,"name":"general"},"inviter":{"id":"2","username":"anon","avatar":null,"discriminator":"0"},"expires_at":null,"uses":4,"max_uses":4,"max_age":0,"temporary":false,"created_at":"2023-01-01T00:00:00.000000+00:00"}"#;
+		let invite: Invite = serde_json::from_str(json).unwrap();
+		assert_eq!(invite.url(), "https://discord.gg/abc");
+		assert_eq!(invite.is_exhausted(), Some(true));
+		assert_eq!(invite.channel.unwrap().name.as_deref(), Some("general"));
+	}
 }
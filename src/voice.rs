@@ -1,4 +1,5 @@
 pub use self::command::Command;
+use crate::event::Recovery;
 use crate::types::SpeakingFlags;
 use crate::{CowString, GuildId, UserId};
 use serde::de;
@@ -17,6 +18,112 @@ impl fmt::Display for EventError {
 
 impl std::error::Error for EventError {}
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CloseCode {
+	UnknownOpcode,
+	FailedToDecode,
+	NotAuthenticated,
+	AuthenticationFailed,
+	AlreadyAuthenticated,
+	SessionNoLongerValid,
+	SessionTimeout,
+	ServerNotFound,
+	UnknownProtocol,
+	Disconnected,
+	VoiceServerCrashed,
+	UnknownEncryptionMode,
+	E2eeRequired,
+	BadRequest,
+	RateLimited,
+	CallTerminated,
+	Other(u16),
+}
+
+impl CloseCode {
+	pub fn code(&self) -> u16 {
+		match self {
+			CloseCode::UnknownOpcode => 4001,
+			CloseCode::FailedToDecode => 4002,
+			CloseCode::NotAuthenticated => 4003,
+			CloseCode::AuthenticationFailed => 4004,
+			CloseCode::AlreadyAuthenticated => 4005,
+			CloseCode::SessionNoLongerValid => 4006,
+			CloseCode::SessionTimeout => 4009,
+			CloseCode::ServerNotFound => 4011,
+			CloseCode::UnknownProtocol => 4012,
+			CloseCode::Disconnected => 4014,
+			CloseCode::VoiceServerCrashed => 4015,
+			CloseCode::UnknownEncryptionMode => 4016,
+			CloseCode::E2eeRequired => 4017,
+			CloseCode::BadRequest => 4020,
+			CloseCode::RateLimited => 4021,
+			CloseCode::CallTerminated => 4022,
+			CloseCode::Other(c) => *c,
+		}
+	}
+
+	pub fn recovery(&self) -> Recovery {
+		match self {
+			CloseCode::NotAuthenticated
+			| CloseCode::SessionNoLongerValid
+			| CloseCode::SessionTimeout => Recovery::Identify,
+			CloseCode::AuthenticationFailed
+			| CloseCode::ServerNotFound
+			| CloseCode::UnknownProtocol
+			| CloseCode::Disconnected
+			| CloseCode::UnknownEncryptionMode
+			| CloseCode::E2eeRequired
+			| CloseCode::BadRequest
+			| CloseCode::RateLimited
+			| CloseCode::CallTerminated => Recovery::Fatal,
+			_ => Recovery::Resume,
+		}
+	}
+
+	pub fn is_fatal(&self) -> bool {
+		self.recovery() == Recovery::Fatal
+	}
+}
+
+impl From<u16> for CloseCode {
+	fn from(code: u16) -> Self {
+		match code {
+			4001 => CloseCode::UnknownOpcode,
+			4002 => CloseCode::FailedToDecode,
+			4003 => CloseCode::NotAuthenticated,
+			4004 => CloseCode::AuthenticationFailed,
+			4005 => CloseCode::AlreadyAuthenticated,
+			4006 => CloseCode::SessionNoLongerValid,
+			4009 => CloseCode::SessionTimeout,
+			4011 => CloseCode::ServerNotFound,
+			4012 => CloseCode::UnknownProtocol,
+			4014 => CloseCode::Disconnected,
+			4015 => CloseCode::VoiceServerCrashed,
+			4016 => CloseCode::UnknownEncryptionMode,
+			4017 => CloseCode::E2eeRequired,
+			4020 => CloseCode::BadRequest,
+			4021 => CloseCode::RateLimited,
+			4022 => CloseCode::CallTerminated,
+			c => CloseCode::Other(c),
+		}
+	}
+}
+
+impl From<CloseCode> for u16 {
+	fn from(code: CloseCode) -> Self {
+		code.code()
+	}
+}
+
+impl fmt::Display for CloseCode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			CloseCode::Other(c) => write!(f, "Other({})", c),
+			c => write!(f, "{:?}({})", c, c.code()),
+		}
+	}
+}
+
 #[derive(Clone, Debug)]
 pub enum Event {
 	Hello(Hello),
@@ -52,17 +159,11 @@ impl Event {
 	}
 
 	pub fn is_heartbeat_ack(&self) -> bool {
-		match self {
-			Event::HeartbeatAck(_) => true,
-			_ => false,
-		}
+		matches!(self, Event::HeartbeatAck(_))
 	}
 
 	pub fn is_ready_kind(&self) -> bool {
-		match self {
-			Event::Ready(_) | Event::Resumed => true,
-			_ => false,
-		}
+		matches!(self, Event::Ready(_) | Event::Resumed)
 	}
 }
 
@@ -184,6 +285,8 @@ pub mod command {
 		Heartbeat(Heartbeat),
 		Speaking(Speaking),
 		Resume(Resume),
+		// Opcode and raw payload of a command the crate doesn't model
+		Unknown(u8, serde_json::Value),
 	}
 
 	impl Command {
@@ -194,14 +297,12 @@ pub mod command {
 				Command::Heartbeat(_) => 3,
 				Command::Speaking(_) => 5,
 				Command::Resume(_) => 7,
+				Command::Unknown(op, _) => *op,
 			}
 		}
 
 		pub fn is_heartbeat(&self) -> bool {
-			match self {
-				Command::Heartbeat(_) => true,
-				_ => false,
-			}
+			matches!(self, Command::Heartbeat(_))
 		}
 	}
 
@@ -218,12 +319,70 @@ pub mod command {
 				Command::Heartbeat(c) => state.serialize_field("d", &c)?,
 				Command::Speaking(c) => state.serialize_field("d", &c)?,
 				Command::Resume(c) => state.serialize_field("d", &c)?,
+				Command::Unknown(_, d) => state.serialize_field("d", d)?,
 			}
 			state.end()
 		}
 	}
 
-	#[derive(Clone, Debug, Serialize)]
+	impl<'de> Deserialize<'de> for Command {
+		fn deserialize<D>(deserializer: D) -> Result<Command, D::Error>
+		where
+			D: Deserializer<'de>,
+		{
+			struct CommandVisitor;
+
+			impl<'de> Visitor<'de> for CommandVisitor {
+				type Value = Command;
+
+				fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+					formatter.write_str("struct Command")
+				}
+
+				fn visit_map<V>(self, mut map: V) -> Result<Command, V::Error>
+				where
+					V: MapAccess<'de>,
+				{
+					let mut op: Option<u8> = None;
+					let mut command = None;
+					while let Some(key) = map.next_key()? {
+						match key {
+							"op" => {
+								if op.is_some() {
+									return Err(de::Error::duplicate_field("op"));
+								}
+								op = Some(map.next_value()?);
+							}
+							"d" => {
+								if command.is_some() {
+									return Err(de::Error::duplicate_field("d"));
+								}
+
+								let op = op.ok_or_else(|| de::Error::missing_field("op"))?;
+								let c = match op {
+									0 => Command::Identify(map.next_value()?),
+									1 => Command::SelectProtocol(map.next_value()?),
+									3 => Command::Heartbeat(map.next_value()?),
+									5 => Command::Speaking(map.next_value()?),
+									7 => Command::Resume(map.next_value()?),
+									e => Command::Unknown(e, map.next_value()?),
+								};
+								command = Some(c);
+							}
+							_ => {
+								map.next_value::<IgnoredAny>()?;
+							}
+						}
+					}
+					command.ok_or_else(|| de::Error::missing_field("d"))
+				}
+			}
+
+			deserializer.deserialize_struct("Command", &["op", "d"], CommandVisitor)
+		}
+	}
+
+	#[derive(Clone, Debug, Deserialize, Serialize)]
 	pub struct Identify {
 		#[serde(rename = "server_id")]
 		pub guild_id: GuildId,
@@ -238,7 +397,7 @@ pub mod command {
 		}
 	}
 
-	#[derive(Clone, Debug, Serialize)]
+	#[derive(Clone, Debug, Deserialize, Serialize)]
 	pub struct Resume {
 		#[serde(rename = "server_id")]
 		pub guild_id: GuildId,
@@ -252,7 +411,7 @@ pub mod command {
 		}
 	}
 
-	#[derive(Clone, Debug, Serialize)]
+	#[derive(Clone, Debug, Deserialize, Serialize)]
 	#[serde(transparent)]
 	pub struct Heartbeat {
 		pub nonce: u64,
@@ -270,16 +429,73 @@ pub mod command {
 		}
 	}
 
-	#[derive(Clone, Debug, Serialize)]
+	#[derive(Clone, Debug, Deserialize, Serialize)]
 	pub struct SelectProtocol {
 		pub protocol: CowString,
 		pub data: SelectProtocolData,
 	}
 
-	#[derive(Clone, Debug, Serialize)]
+	#[derive(Clone, Debug, Deserialize, Serialize)]
 	pub struct SelectProtocolData {
 		pub address: CowString,
 		pub port: u16,
 		pub mode: CowString,
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn round_trip(json: &str) -> Command {
+		let command: Command = serde_json::from_str(json).unwrap();
+		let value = serde_json::to_value(&command).unwrap();
+		let original: serde_json::Value = serde_json::from_str(json).unwrap();
+		assert_eq!(value, original);
+		command
+	}
+
+	#[test]
+	fn command() {
+		match round_trip(
+			r#"{"op":0,"d":{"server_id":"1","user_id":"2","session_id":"abc","token":"tok"}}"#,
+		) {
+			Command::Identify(i) => {
+				assert_eq!(i.guild_id, GuildId::from(1));
+				assert_eq!(i.user_id, UserId::from(2));
+			}
+			c => panic!("unexpected command {:?}", c),
+		}
+		round_trip(
+			r#"{"op":1,"d":{"protocol":"udp","data":{"address":"127.0.0.1","port":1234,"mode":"xsalsa20_poly1305"}}}"#,
+		);
+		assert!(round_trip(r#"{"op":3,"d":42}"#).is_heartbeat());
+		round_trip(r#"{"op":5,"d":{"speaking":1,"delay":0,"ssrc":7}}"#);
+		round_trip(r#"{"op":7,"d":{"server_id":"1","session_id":"abc","token":"tok"}}"#);
+		match round_trip(r#"{"op":16,"d":{"max_dave_protocol_version":1}}"#) {
+			Command::Unknown(16, d) => assert_eq!(d["max_dave_protocol_version"], 1),
+			c => panic!("unexpected command {:?}", c),
+		}
+		assert!(serde_json::from_str::<Command>(r#"{"op":0}"#).is_err());
+	}
+
+	#[test]
+	fn close_code() {
+		for code in 4001..=4022 {
+			assert_eq!(CloseCode::from(code).code(), code);
+		}
+		assert_eq!(CloseCode::from(4007), CloseCode::Other(4007));
+		assert_eq!(CloseCode::from(1000), CloseCode::Other(1000));
+		assert_eq!(CloseCode::from(4015), CloseCode::VoiceServerCrashed);
+		assert_eq!(CloseCode::from(4015).recovery(), Recovery::Resume);
+		assert_eq!(CloseCode::from(4001).recovery(), Recovery::Resume);
+		assert_eq!(CloseCode::Other(4999).recovery(), Recovery::Resume);
+		assert_eq!(CloseCode::from(4006).recovery(), Recovery::Identify);
+		assert_eq!(CloseCode::from(4009).recovery(), Recovery::Identify);
+		assert!(CloseCode::from(4014).is_fatal());
+		assert!(CloseCode::from(4004).is_fatal());
+		assert!(!CloseCode::from(4006).is_fatal());
+		assert_eq!(CloseCode::RateLimited.to_string(), "RateLimited(4021)");
+		assert_eq!(CloseCode::Other(4999).to_string(), "Other(4999)");
+	}
+}